@@ -0,0 +1,190 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+use crate::Part;
+
+// Known answers are kept in a small TOML file, one table per day :
+//
+//   [day_07]
+//   part_1 = "3749"
+//   part_2 = "11387"
+//
+// Only this subset of TOML is read and written, answers are always stored as strings.
+
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+  Pass,
+  Fail { expected: String },
+  Unknown,
+}
+
+impl fmt::Display for Verdict {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    match self {
+      Verdict::Pass => write!( f, "pass" ),
+      Verdict::Fail { expected } => write!( f, "FAIL (expected {expected})" ),
+      Verdict::Unknown => write!( f, "unknown" ),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+  Io(io::Error),
+  Syntax { line: usize, message: String },
+}
+
+impl fmt::Display for AnswersError {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    match self {
+      AnswersError::Io(error) => write!( f, "{error}" ),
+      AnswersError::Syntax { line, message } => write!( f, "line {line}: {message}" ),
+    }
+  }
+}
+
+impl std::error::Error for AnswersError {}
+
+impl From<io::Error> for AnswersError {
+  fn from( error: io::Error ) -> Self {
+    AnswersError::Io( error )
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AnswerStore {
+  answers: BTreeMap<(u8, Part), String>,
+}
+
+impl AnswerStore {
+  /// Loads the store at `path`, a missing file being an empty store.
+  pub fn load( path: impl AsRef<Path> ) -> Result<Self, AnswersError> {
+    match fs::read_to_string( path ) {
+      Ok(text) => text.parse(),
+      Err(error) if error.kind() == io::ErrorKind::NotFound => Ok( Self::default() ),
+      Err(error) => Err( error.into() ),
+    }
+  }
+
+  pub fn save( &self, path: impl AsRef<Path> ) -> io::Result<()> {
+    fs::write( path, self.to_string() )
+  }
+
+  pub fn get( &self, day: u8, part: Part ) -> Option<&str> {
+    self.answers.get( &(day, part) ).map( String::as_str )
+  }
+
+  /// Records `answer`, returning the previously known answer if it changed.
+  pub fn record( &mut self, day: u8, part: Part, answer: &str ) -> Option<String> {
+    self.answers.insert( (day, part), answer.to_owned() )
+      .filter( |previous| previous != answer )
+  }
+
+  pub fn check( &self, day: u8, part: Part, answer: &str ) -> Verdict {
+    match self.get( day, part ) {
+      Some(expected) if expected == answer => Verdict::Pass,
+      Some(expected) => Verdict::Fail { expected: expected.to_owned() },
+      None => Verdict::Unknown,
+    }
+  }
+}
+
+impl fmt::Display for AnswerStore {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    let mut current_day = None;
+    for ((day, part), answer) in &self.answers {
+      if current_day != Some(*day) {
+        if current_day.is_some() {
+          writeln!( f )?;
+        }
+        writeln!( f, "[day_{day:02}]" )?;
+        current_day = Some( *day );
+      }
+      writeln!( f, "part_{part} = \"{answer}\"" )?;
+    }
+    Ok(())
+  }
+}
+
+impl std::str::FromStr for AnswerStore {
+  type Err = AnswersError;
+
+  fn from_str( input: &str ) -> Result<Self, Self::Err> {
+    let mut store = Self::default();
+    let mut day = None;
+    for (idx, line) in input.lines().enumerate() {
+      let error = |message: &str| AnswersError::Syntax { line: idx+1, message: message.to_owned() };
+      let line = line.split( '#' ).next().unwrap_or_default().trim();
+      if line.is_empty() {
+        continue;
+      }
+
+      if let Some(table) = line.strip_prefix( '[' ).and_then( |line| line.strip_suffix(']') ) {
+        let number = table.trim().strip_prefix( "day_" )
+          .and_then( |number| number.parse().ok() )
+          .ok_or_else( || error( "expected a [day_NN] table" ) )?;
+        day = Some( number );
+        continue;
+      }
+
+      let day = day.ok_or_else( || error( "answer outside of a [day_NN] table" ) )?;
+      let (key, value) = line.split_once( '=' )
+        .ok_or_else( || error( "expected part_N = \"answer\"" ) )?;
+      let part = key.trim().strip_prefix( "part_" )
+        .and_then( |part| part.parse::<Part>().ok() )
+        .ok_or_else( || error( "expected part_1 or part_2" ) )?;
+      let value = value.trim();
+      let answer = value.strip_prefix( '"' ).and_then( |value| value.strip_suffix('"') )
+        .unwrap_or( value );
+      store.answers.insert( (day, part), answer.to_owned() );
+    }
+    Ok( store )
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const TEST_ANSWERS: &str = "[day_01]
+part_1 = \"11\"
+part_2 = \"31\"
+
+[day_07]
+part_1 = \"3749\"
+";
+
+  #[test]
+  fn test_round_trip() {
+    let store = TEST_ANSWERS.parse::<AnswerStore>().unwrap();
+    assert_eq!( Some("31"), store.get(1, Part::Two) );
+    assert_eq!( None, store.get(7, Part::Two) );
+    assert_eq!( TEST_ANSWERS, store.to_string() );
+  }
+
+  #[test]
+  fn test_check() {
+    let mut store = TEST_ANSWERS.parse::<AnswerStore>().unwrap();
+    assert_eq!( Verdict::Pass, store.check(1, Part::One, "11") );
+    assert_eq!( Verdict::Fail { expected: "11".to_owned() }, store.check(1, Part::One, "12") );
+    assert_eq!( Verdict::Unknown, store.check(7, Part::Two, "11387") );
+
+    assert_eq!( None, store.record(7, Part::Two, "11387") );
+    assert_eq!( None, store.record(7, Part::Two, "11387") );
+    assert_eq!( Some("11387".to_owned()), store.record(7, Part::Two, "0") );
+  }
+
+  #[test]
+  fn test_syntax_error() {
+    let error = "part_1 = \"11\"".parse::<AnswerStore>().unwrap_err();
+    assert!( matches!( error, AnswersError::Syntax { line: 1, .. } ) );
+    let error = "[day_01]\n\npart_3 = \"11\"".parse::<AnswerStore>().unwrap_err();
+    assert!( matches!( error, AnswersError::Syntax { line: 3, .. } ) );
+  }
+}
@@ -0,0 +1,139 @@
+use std::{fmt, hint, time::{Duration, Instant}};
+use crate::{ParseError, Solution};
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+  pub min: Duration,
+  pub median: Duration,
+  pub mean: Duration,
+}
+
+impl Stats {
+  pub fn from_samples( mut samples: Vec<Duration> ) -> Self {
+    assert!( !samples.is_empty(), "should have at least one sample" );
+    samples.sort_unstable();
+    let len = samples.len();
+    let median = match len % 2 {
+      0 => (samples[ len/2 - 1 ] + samples[ len/2 ]) / 2,
+      _ => samples[ len/2 ],
+    };
+    let mean = samples.iter().sum::<Duration>() / len as u32;
+    Self { min: samples[0], median, mean }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayBench {
+  pub day: u8,
+  pub runs: usize,
+  pub parse: Stats,
+  pub part_one: Stats,
+  pub part_two: Stats,
+}
+
+impl DayBench {
+  fn phases( &self ) -> [(&'static str, &Stats); 3] {
+    [("parse", &self.parse), ("part_one", &self.part_one), ("part_two", &self.part_two)]
+  }
+}
+
+fn time<T>( runs: usize, mut f: impl FnMut() -> T ) -> Stats {
+  let samples = (0..runs)
+    .map( |_| {
+      let now = Instant::now();
+      hint::black_box( f() );
+      now.elapsed()
+    })
+    .collect();
+  Stats::from_samples( samples )
+}
+
+/// Times parse, part one and part two separately, each of them `runs` times.
+pub fn bench<S: Solution>( input: &str, runs: usize ) -> Result<DayBench, ParseError> {
+  let runs = runs.max( 1 );
+  let parsed = S::parse( input )?;
+  let parse = time( runs, || S::parse( hint::black_box(input) ) );
+  let part_one = time( runs, || S::part_one( hint::black_box(&parsed) ) );
+  let part_two = time( runs, || S::part_two( hint::black_box(&parsed) ) );
+  Ok( DayBench { day: S::DAY, runs, parse, part_one, part_two } )
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Human readable table, one line per day and phase.
+pub struct Table<'a>( pub &'a [DayBench] );
+
+impl fmt::Display for Table<'_> {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    writeln!( f, "{:<5} {:<9} {:>12} {:>12} {:>12}", "day", "phase", "min", "median", "mean" )?;
+    for bench in self.0 {
+      for (phase, stats) in bench.phases() {
+        writeln!( f, "{:<5} {:<9} {:>12} {:>12} {:>12}",
+          format!( "{:02}", bench.day ), phase,
+          format!( "{:.3?}", stats.min ), format!( "{:.3?}", stats.median ), format!( "{:.3?}", stats.mean ) )?;
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Stable JSON rendering, durations in nanoseconds, one line per phase so two runs diff cleanly.
+pub struct Json<'a>( pub &'a [DayBench] );
+
+impl fmt::Display for Json<'_> {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    writeln!( f, "[" )?;
+    for (idx, bench) in self.0.iter().enumerate() {
+      writeln!( f, "  {{" )?;
+      writeln!( f, "    \"day\": {},", bench.day )?;
+      writeln!( f, "    \"runs\": {},", bench.runs )?;
+      for (phase_idx, (phase, stats)) in bench.phases().into_iter().enumerate() {
+        let separator = if phase_idx < 2 { "," } else { "" };
+        writeln!( f, "    \"{phase}\": {{ \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {} }}{separator}",
+          stats.min.as_nanos(), stats.median.as_nanos(), stats.mean.as_nanos() )?;
+      }
+      let separator = if idx+1 < self.0.len() { "," } else { "" };
+      writeln!( f, "  }}{separator}" )?;
+    }
+    writeln!( f, "]" )
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_stats() {
+    let samples = [4, 1, 3, 2].map( Duration::from_millis ).to_vec();
+    let stats = Stats::from_samples( samples );
+    assert_eq!( Duration::from_millis(1), stats.min );
+    assert_eq!( Duration::from_micros(2500), stats.median );
+    assert_eq!( Duration::from_micros(2500), stats.mean );
+
+    let samples = [5, 1, 9].map( Duration::from_millis ).to_vec();
+    let stats = Stats::from_samples( samples );
+    assert_eq!( Duration::from_millis(5), stats.median );
+  }
+
+  #[test]
+  fn test_json() {
+    let stats = Stats { min: Duration::from_nanos(1), median: Duration::from_nanos(2), mean: Duration::from_nanos(3) };
+    let bench = DayBench { day: 7, runs: 3, parse: stats, part_one: stats, part_two: stats };
+    let expected = r#"[
+  {
+    "day": 7,
+    "runs": 3,
+    "parse": { "min_ns": 1, "median_ns": 2, "mean_ns": 3 },
+    "part_one": { "min_ns": 1, "median_ns": 2, "mean_ns": 3 },
+    "part_two": { "min_ns": 1, "median_ns": 2, "mean_ns": 3 }
+  }
+]
+"#;
+    assert_eq!( expected, Json(&[bench]).to_string() );
+  }
+}
@@ -0,0 +1,315 @@
+use std::{fs, io::{self, IsTerminal, Write}, path::Path, process, time::Duration};
+use advent_of_code_2024::{
+  answers::{AnswerStore, Verdict, DEFAULT_ANSWERS_FILE},
+  bench::{Json, Table},
+  input::{InputError, InputResolver},
+  parallel::*,
+  render::Canvas,
+  trace::Trace,
+  Answers, Day, ParseError, Part, DAYS,
+};
+
+const USAGE: &str = "usage:
+  aoc run    [--day <1-25>] [--part <1|2>] [--input <path|->] [--record] [--answers <path>]
+             [--visualize] [--image <path.ppm|path.pgm>]
+  aoc verify [--day <1-25>] [--part <1|2>] [--input <path|->] [--answers <path>]
+  aoc bench  [--day <1-25>] [--input <path|->] [--runs <n>] [--json <path>]
+  aoc trace  --day <1-25> [--part <1|2>] [--input <path|->] [--frames <n>]
+             [--replay] [--delay <ms>] [--asciicast <path>] [--jsonl <path>]
+  aoc report --day <1-25> [--part <1|2>] [--input <path|->] [--json <path>]";
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct Args {
+  day: Option<u8>,
+  part: Option<Part>,
+  input: Option<String>,
+  answers: Option<String>,
+  record: bool,
+  runs: Option<usize>,
+  json: Option<String>,
+  visualize: bool,
+  image: Option<String>,
+  frames: Option<usize>,
+  replay: bool,
+  delay: Option<u64>,
+  asciicast: Option<String>,
+  jsonl: Option<String>,
+}
+
+impl Args {
+  fn days( &self ) -> Result<Vec<Day>, String> {
+    match self.day {
+      Some(number) => Ok( vec!( Day::find( number ).ok_or_else( || format!( "day {number} is not solved yet" ) )? ) ),
+      None => Ok( DAYS.to_vec() ),
+    }
+  }
+
+  fn parts( &self ) -> Vec<Part> {
+    match self.part {
+      Some(part) => vec!( part ),
+      None => Part::PARTS.to_vec(),
+    }
+  }
+
+  fn resolver( &self ) -> InputResolver {
+    let mut resolver = InputResolver::from_env();
+    if let Some(path) = &self.input {
+      resolver = resolver.with_path( path );
+    }
+    if self.day.is_none() {
+      // a single stdin can't feed every day
+      resolver = resolver.with_stdin( false );
+    }
+    resolver
+  }
+
+  fn answers_path( &self ) -> &str {
+    self.answers.as_deref().unwrap_or( DEFAULT_ANSWERS_FILE )
+  }
+}
+
+fn parse_args( mut args: impl Iterator<Item=String> ) -> Result<Args, String> {
+  let mut parsed = Args::default();
+  while let Some(arg) = args.next() {
+    let mut value = || args.next()
+      .ok_or_else( || format!( "missing value for '{arg}'" ) );
+    match arg.as_str() {
+      "--day" => {
+        let day = value()?;
+        parsed.day = Some( day.parse().map_err( |_| format!( "invalid day '{day}'" ) )? );
+      },
+      "--part" => parsed.part = Some( value()?.parse()? ),
+      "--input" => parsed.input = Some( value()? ),
+      "--answers" => parsed.answers = Some( value()? ),
+      "--record" => parsed.record = true,
+      "--runs" => {
+        let runs = value()?;
+        parsed.runs = Some( runs.parse().map_err( |_| format!( "invalid number of runs '{runs}'" ) )? );
+      },
+      "--json" => parsed.json = Some( value()? ),
+      "--visualize" => parsed.visualize = true,
+      "--image" => parsed.image = Some( value()? ),
+      "--frames" => {
+        let frames = value()?;
+        parsed.frames = Some( frames.parse().map_err( |_| format!( "invalid number of frames '{frames}'" ) )? );
+      },
+      "--replay" => parsed.replay = true,
+      "--delay" => {
+        let delay = value()?;
+        parsed.delay = Some( delay.parse().map_err( |_| format!( "invalid delay '{delay}'" ) )? );
+      },
+      "--asciicast" => parsed.asciicast = Some( value()? ),
+      "--jsonl" => parsed.jsonl = Some( value()? ),
+      _ => return Err( format!( "unexpected argument '{arg}'" ) ),
+    }
+  }
+  if parsed.input.is_some() && parsed.day.is_none() {
+    return Err( "'--input' requires '--day'".to_owned() );
+  }
+  if parsed.image.is_some() && parsed.day.is_none() {
+    return Err( "'--image' requires '--day'".to_owned() );
+  }
+  Ok( parsed )
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+pub fn main() {
+  let mut args = std::env::args().skip( 1 );
+  let result = match args.next().as_deref() {
+    Some("run") => parse_args( args ).and_then( |args| run(&args) ),
+    Some("verify") => parse_args( args ).and_then( |args| verify(&args) ),
+    Some("bench") => parse_args( args ).and_then( |args| bench(&args) ),
+    Some("trace") => parse_args( args ).and_then( |args| trace(&args) ),
+    Some("report") => parse_args( args ).and_then( |args| report(&args) ),
+    Some(command) => Err( format!( "unknown command '{command}'\n{USAGE}" ) ),
+    None => Err( USAGE.to_owned() ),
+  };
+  if let Err(error) = result {
+    eprintln!( "error: {error}" );
+    process::exit( 1 );
+  }
+}
+
+/// Reads the day's input, `None` meaning it is missing and the day should be skipped.
+fn read_input( args: &Args, resolver: &InputResolver, day: Day ) -> Result<Option<String>, String> {
+  match resolver.resolve( day.number ) {
+    Ok(input) => Ok( Some(input.text) ),
+    Err(InputError::NotFound { .. }) if args.day.is_none() => {
+      eprintln!( "day {:02}: no input, skipped", day.number );
+      Ok( None )
+    },
+    Err(error) => Err( error.to_string() ),
+  }
+}
+
+fn parse_failure( day: Day, error: &ParseError ) -> String {
+  format!( "failed to parse day {:02} input, {}", day.number, error.render() )
+}
+
+/// Solves every day having an input, concurrently with the `parallel` feature, results being kept in day order.
+fn solve( args: &Args, days: &[Day], parts: &[Part], resolver: &InputResolver ) -> Result<Vec<(Day, String, Answers)>, String> {
+  let mut inputs = Vec::new();
+  for day in days {
+    if let Some(input) = read_input( args, resolver, *day )? {
+      inputs.push( (*day, input) );
+    }
+  }
+  let answers = inputs.maybe_par_iter()
+    .map( |(day, input)| day.run( input, parts ) )
+    .collect::<Vec<_>>();
+  // errors are reported serially, so that the first failing day is the one reported
+  std::iter::zip( inputs, answers )
+    .map( |((day, input), answers)| Ok( (day, input, answers.map_err( |error| parse_failure(day, &error) )?) ) )
+    .collect()
+}
+
+fn run( args: &Args ) -> Result<(), String> {
+  let (days, parts, resolver) = (args.days()?, args.parts(), args.resolver());
+  let mut answers = match args.record {
+    true => Some( AnswerStore::load( args.answers_path() ).map_err( |error| format!( "{}: {error}", args.answers_path() ) )? ),
+    false => None,
+  };
+
+  for (day, input, day_answers) in solve( args, &days, &parts, &resolver )? {
+    for (part, answer) in day_answers {
+      println!( "day {:02} part {part}: {answer}", day.number );
+      if let Some(previous) = answers.as_mut().and_then( |answers| answers.record( day.number, part, &answer ) ) {
+        eprintln!( "day {:02} part {part}: replaced previously recorded answer {previous}", day.number );
+      }
+    }
+    if args.visualize || args.image.is_some() {
+      visualize( args, day, &input )?;
+    }
+  }
+
+  if let Some(answers) = answers {
+    answers.save( args.answers_path() )
+      .map_err( |error| format!( "failed to write {}: {error}", args.answers_path() ) )?;
+  }
+  Ok(())
+}
+
+fn visualize( args: &Args, day: Day, input: &str ) -> Result<(), String> {
+  const IMAGE_SCALE: usize = 4;
+
+  let Some(canvas) = day.visualize( input ).map_err( |error| parse_failure(day, &error) )? else {
+    eprintln!( "day {:02}: no visualization", day.number );
+    return Ok(());
+  };
+  if args.visualize {
+    match io::stdout().is_terminal() {
+      true => print!( "{}", canvas.ansi() ),
+      false => print!( "{canvas}" ),
+    }
+  }
+  if let Some(path) = &args.image {
+    write_image( &canvas, Path::new(path), IMAGE_SCALE )
+      .map_err( |error| format!( "failed to write {path}: {error}" ) )?;
+  }
+  Ok(())
+}
+
+/// Greyscale PGM when `path` ends with `.pgm`, RGB PPM otherwise.
+fn write_image( canvas: &Canvas, path: &Path, scale: usize ) -> io::Result<()> {
+  let mut writer = io::BufWriter::new( fs::File::create(path)? );
+  match path.extension().and_then( |extension| extension.to_str() ) {
+    Some("pgm") => canvas.write_pgm( &mut writer, scale )?,
+    _ => canvas.write_ppm( &mut writer, scale )?,
+  }
+  writer.flush()
+}
+
+fn verify( args: &Args ) -> Result<(), String> {
+  let (days, parts, resolver) = (args.days()?, args.parts(), args.resolver());
+  let answers = AnswerStore::load( args.answers_path() )
+    .map_err( |error| format!( "{}: {error}", args.answers_path() ) )?;
+
+  let mut failures = 0;
+  for (day, _, day_answers) in solve( args, &days, &parts, &resolver )? {
+    for (part, answer) in day_answers {
+      let verdict = answers.check( day.number, part, &answer );
+      println!( "day {:02} part {part}: {answer} {verdict}", day.number );
+      if matches!( verdict, Verdict::Fail { .. } ) {
+        failures += 1;
+      }
+    }
+  }
+
+  match failures {
+    0 => Ok(()),
+    _ => Err( format!( "{failures} answer(s) differ from {}", args.answers_path() ) ),
+  }
+}
+
+fn bench( args: &Args ) -> Result<(), String> {
+  const DEFAULT_RUNS: usize = 10;
+
+  let (days, resolver) = (args.days()?, args.resolver());
+  let runs = args.runs.unwrap_or( DEFAULT_RUNS );
+
+  let mut benches = Vec::new();
+  for day in days {
+    let Some(input) = read_input( args, &resolver, day )? else { continue };
+    benches.push( day.bench( &input, runs ).map_err( |error| parse_failure(day, &error) )? );
+  }
+
+  print!( "{}", Table(&benches) );
+  if let Some(path) = &args.json {
+    fs::write( path, Json(&benches).to_string() )
+      .map_err( |error| format!( "failed to write {path}: {error}" ) )?;
+  }
+  Ok(())
+}
+
+fn trace( args: &Args ) -> Result<(), String> {
+  const DEFAULT_FRAMES: usize = 1000;
+  const DEFAULT_DELAY_MS: u64 = 100;
+
+  if args.day.is_none() {
+    return Err( "'trace' requires '--day'".to_owned() );
+  }
+  let (day, part, resolver) = (args.days()?[0], args.part.unwrap_or( Part::One ), args.resolver());
+  let Some(input) = read_input( args, &resolver, day )? else { return Ok(()) };
+
+  let mut trace = Trace::with_limit( args.frames.unwrap_or( DEFAULT_FRAMES ) );
+  if !day.trace( &input, part, &mut trace ).map_err( |error| parse_failure(day, &error) )? {
+    return Err( format!( "day {:02} part {part} has no simulation to trace", day.number ) );
+  }
+
+  let delay = Duration::from_millis( args.delay.unwrap_or( DEFAULT_DELAY_MS ) );
+  if let Some(path) = &args.asciicast {
+    fs::write( path, trace.asciicast( delay ).to_string() )
+      .map_err( |error| format!( "failed to write {path}: {error}" ) )?;
+  }
+  if let Some(path) = &args.jsonl {
+    fs::write( path, trace.json_lines().to_string() )
+      .map_err( |error| format!( "failed to write {path}: {error}" ) )?;
+  }
+  if args.replay || (args.asciicast.is_none() && args.jsonl.is_none()) {
+    trace.replay( &mut io::stdout().lock(), delay )
+      .map_err( |error| format!( "failed to replay: {error}" ) )?;
+  }
+  eprintln!( "day {:02} part {part}: {} steps, {} frames kept", day.number, trace.steps(), trace.frames().len() );
+  Ok(())
+}
+
+fn report( args: &Args ) -> Result<(), String> {
+  if args.day.is_none() {
+    return Err( "'report' requires '--day'".to_owned() );
+  }
+  let (day, part, resolver) = (args.days()?[0], args.part.unwrap_or( Part::One ), args.resolver());
+  let Some(input) = read_input( args, &resolver, day )? else { return Ok(()) };
+
+  let Some(report) = day.report( &input, part ).map_err( |error| parse_failure(day, &error) )? else {
+    return Err( format!( "day {:02} part {part} has no report", day.number ) );
+  };
+  print!( "{report}" );
+  if let Some(path) = &args.json {
+    fs::write( path, report.json() + "\n" )
+      .map_err( |error| format!( "failed to write {path}: {error}" ) )?;
+  }
+  Ok(())
+}
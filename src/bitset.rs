@@ -0,0 +1,188 @@
+// Fixed size set of small integers packed in u64 words, for visited-state tracking where a HashSet would dominate,
+// and its two dimensional counterpart for dense relations between small integers.
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+  words: Vec<u64>,
+  capacity: usize,
+}
+
+impl BitSet {
+  /// Empty set able to hold `0..capacity`.
+  pub fn new( capacity: usize ) -> Self {
+    Self { words: vec![0; capacity.div_ceil( 64 )], capacity }
+  }
+
+  pub fn capacity( &self ) -> usize {
+    self.capacity
+  }
+
+  /// Adds `value`, returning whether it was absent. Panics if `value` is out of capacity.
+  pub fn insert( &mut self, value: usize ) -> bool {
+    assert!( value < self.capacity, "{value} is out of the bitset capacity {}", self.capacity );
+    let (word, mask) = (value / 64, 1 << (value % 64));
+    let absent = self.words[ word ] & mask == 0;
+    self.words[ word ] |= mask;
+    absent
+  }
+
+  pub fn remove( &mut self, value: usize ) -> bool {
+    let Some(word) = self.words.get_mut( value / 64 ) else { return false };
+    let mask = 1 << (value % 64);
+    let present = *word & mask != 0;
+    *word &= !mask;
+    present
+  }
+
+  pub fn contains( &self, value: usize ) -> bool {
+    self.words.get( value / 64 )
+      .is_some_and( |word| word & (1 << (value % 64)) != 0 )
+  }
+
+  pub fn clear( &mut self ) {
+    self.words.fill( 0 );
+  }
+
+  pub fn len( &self ) -> usize {
+    self.words.iter().map( |word| word.count_ones() as usize ).sum()
+  }
+
+  pub fn is_empty( &self ) -> bool {
+    self.words.iter().all( |word| *word == 0 )
+  }
+
+  /// Values in increasing order.
+  pub fn iter( &self ) -> impl Iterator<Item=usize> + '_ {
+    self.words.iter().enumerate()
+      .flat_map( |(idx, word)| (0..64)
+        .filter( move |bit| word & (1 << bit) != 0 )
+        .map( move |bit| idx * 64 + bit ) )
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Fixed size matrix of bits, each row packed in its own u64 words so that rows can be combined word by word.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+  rows: usize,
+  cols: usize,
+  stride: usize,
+  words: Vec<u64>,
+}
+
+impl BitMatrix {
+  pub fn new( rows: usize, cols: usize ) -> Self {
+    let stride = cols.div_ceil( 64 );
+    Self { rows, cols, stride, words: vec![0; rows * stride] }
+  }
+
+  pub fn rows( &self ) -> usize {
+    self.rows
+  }
+
+  pub fn cols( &self ) -> usize {
+    self.cols
+  }
+
+  /// Sets the bit at (`row`, `col`), returning whether it was unset. Panics if out of bounds.
+  pub fn insert( &mut self, row: usize, col: usize ) -> bool {
+    assert!( row < self.rows && col < self.cols, "({row}, {col}) is out of the {}x{} bit matrix", self.rows, self.cols );
+    let (word, mask) = (row * self.stride + col / 64, 1 << (col % 64));
+    let absent = self.words[ word ] & mask == 0;
+    self.words[ word ] |= mask;
+    absent
+  }
+
+  /// Whether the bit at (`row`, `col`) is set, false out of bounds.
+  pub fn contains( &self, row: usize, col: usize ) -> bool {
+    row < self.rows && col < self.cols
+      && self.words[ row * self.stride + col / 64 ] & (1 << (col % 64)) != 0
+  }
+
+  /// Columns set in `row`, in increasing order.
+  pub fn row( &self, row: usize ) -> impl Iterator<Item=usize> + '_ {
+    self.words[ row * self.stride..(row + 1) * self.stride ].iter().enumerate()
+      .flat_map( |(idx, word)| (0..64)
+        .filter( move |bit| word & (1 << bit) != 0 )
+        .map( move |bit| idx * 64 + bit ) )
+  }
+
+  /// Sets in row `into` every bit set in row `from`.
+  pub fn union_rows( &mut self, into: usize, from: usize ) {
+    for col in 0..self.stride {
+      self.words[ into * self.stride + col ] |= self.words[ from * self.stride + col ];
+    }
+  }
+
+  pub fn len( &self ) -> usize {
+    self.words.iter().map( |word| word.count_ones() as usize ).sum()
+  }
+
+  pub fn is_empty( &self ) -> bool {
+    self.words.iter().all( |word| *word == 0 )
+  }
+
+  /// For a square matrix seen as a relation, the relation's transitive closure, with Warshall's algorithm.
+  pub fn transitive_closure( &self ) -> Self {
+    assert_eq!( self.rows, self.cols, "only a square matrix has a transitive closure" );
+    let mut closure = self.clone();
+    for through in 0..self.rows {
+      for row in 0..self.rows {
+        if closure.contains( row, through ) {
+          closure.union_rows( row, through );
+        }
+      }
+    }
+    closure
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_bitset() {
+    let mut set = BitSet::new( 130 );
+    assert!( set.is_empty() );
+    assert!( set.insert( 3 ) );
+    assert!( !set.insert( 3 ) );
+    assert!( set.insert( 64 ) );
+    assert!( set.insert( 129 ) );
+    assert!( set.contains( 64 ) && !set.contains( 65 ) && !set.contains( 1000 ) );
+    assert_eq!( vec![3, 64, 129], set.iter().collect::<Vec<_>>() );
+    assert!( set.remove( 64 ) && !set.remove( 64 ) );
+    assert_eq!( 2, set.len() );
+    set.clear();
+    assert!( set.is_empty() );
+  }
+
+  #[test]
+  fn test_bit_matrix() {
+    let mut matrix = BitMatrix::new( 3, 70 );
+    assert!( matrix.insert( 0, 1 ) && matrix.insert( 0, 69 ) && !matrix.insert( 0, 69 ) );
+    assert!( matrix.contains( 0, 69 ) && !matrix.contains( 1, 69 ) && !matrix.contains( 5, 1 ) );
+    assert_eq!( vec![1, 69], matrix.row( 0 ).collect::<Vec<_>>() );
+    matrix.union_rows( 2, 0 );
+    assert_eq!( vec![1, 69], matrix.row( 2 ).collect::<Vec<_>>() );
+    assert_eq!( 4, matrix.len() );
+  }
+
+  #[test]
+  fn test_transitive_closure() {
+    // 0 -> 1 -> 2 -> 3, and 4 -> 4
+    let mut relation = BitMatrix::new( 5, 5 );
+    for (from, to) in [(0, 1), (1, 2), (2, 3), (4, 4)] {
+      relation.insert( from, to );
+    }
+    let closure = relation.transitive_closure();
+    assert_eq!( vec![1, 2, 3], closure.row( 0 ).collect::<Vec<_>>() );
+    assert_eq!( vec![3], closure.row( 2 ).collect::<Vec<_>>() );
+    assert_eq!( 7, closure.len() );
+  }
+}
@@ -0,0 +1,327 @@
+use std::{collections::{hash_map::Entry, HashMap}, fmt, hash::{BuildHasherDefault, Hash, Hasher}, slice};
+use crate::search::Graph;
+
+// Memoized dynamic programming over directed acyclic graphs : nodes are kept in topological order so that
+// every value is computed once, from the already computed values of its successors.
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+  /// A node lying on a cycle, or only reachable through one.
+  pub node: N,
+}
+
+impl<N: fmt::Debug> fmt::Display for CycleError<N> {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    write!( f, "graph is not acyclic, cycle reaching {:?}", self.node )
+  }
+}
+
+impl<N: fmt::Debug> std::error::Error for CycleError<N> {}
+
+/// Multiplicative hashing of the words of a node, much faster than the default hasher on the small keys nodes are,
+/// with no need for its resistance to crafted keys.
+#[derive(Default)]
+struct NodeHasher( u64 );
+
+impl Hasher for NodeHasher {
+  fn write( &mut self, bytes: &[u8] ) {
+    for byte in bytes {
+      self.write_u64( u64::from( *byte ) );
+    }
+  }
+
+  fn write_u64( &mut self, word: u64 ) {
+    self.0 = (self.0.rotate_left( 5 ) ^ word).wrapping_mul( 0x517c_c1b7_2722_0a95 );
+  }
+
+  fn write_usize( &mut self, word: usize ) {
+    self.write_u64( word as u64 );
+  }
+
+  fn finish( &self ) -> u64 {
+    self.0
+  }
+}
+
+type NodeMap<N> = HashMap<N, usize, BuildHasherDefault<NodeHasher>>;
+
+#[derive(Debug, Clone)]
+pub struct Dag<N> {
+  /// Nodes in topological order, successors always coming after their predecessors.
+  nodes: Vec<N>,
+  index: NodeMap<N>,
+  /// Successors of the node at `idx` in `nodes`, as indices there too : `edges[ offsets[idx]..offsets[idx+1] ]`.
+  offsets: Vec<usize>,
+  edges: Vec<usize>,
+}
+
+impl<N> Dag<N> where N: Copy + Eq + Hash {
+  /// Every node reachable from `roots` in `graph`, sorted with Kahn's algorithm.
+  pub fn new<G>( graph: &G, roots: impl IntoIterator<Item=N> ) -> Result<Self, CycleError<N>> where G: Graph<Node=N> {
+    let roots = roots.into_iter();
+    let mut nodes = Vec::with_capacity( roots.size_hint().0 );
+    let mut index = NodeMap::with_capacity_and_hasher( roots.size_hint().0, Default::default() );
+    // successors of the node found at `idx` as the range `ranges[idx]` of `found_edges`, nodes being numbered as found
+    let (mut ranges, mut found_edges) = (Vec::with_capacity( roots.size_hint().0 ), Vec::new());
+
+    let mut stack = Vec::new();
+    for root in roots {
+      if let Entry::Vacant(entry) = index.entry( root ) {
+        entry.insert( nodes.len() );
+        nodes.push( root );
+        ranges.push( 0..0 );
+        stack.push( root );
+      }
+    }
+    while let Some(node) = stack.pop() {
+      let (from, start) = (index[ &node ], found_edges.len());
+      for (next, _) in graph.neighbours( node ) {
+        let to = *index.entry( next ).or_insert_with( || {
+          nodes.push( next );
+          ranges.push( 0..0 );
+          stack.push( next );
+          nodes.len() - 1
+        });
+        found_edges.push( to );
+      }
+      ranges[ from ] = start..found_edges.len();
+    }
+
+    let mut in_degrees = vec![0; nodes.len()];
+    for to in &found_edges {
+      in_degrees[ *to ] += 1;
+    }
+    let mut ready = (0..nodes.len())
+      .filter( |idx| in_degrees[*idx] == 0 )
+      .collect::<Vec<_>>();
+    let mut order = Vec::with_capacity( nodes.len() );
+    while let Some(idx) = ready.pop() {
+      order.push( idx );
+      for to in &found_edges[ ranges[idx].clone() ] {
+        in_degrees[ *to ] -= 1;
+        if in_degrees[ *to ] == 0 {
+          ready.push( *to );
+        }
+      }
+    }
+    if order.len() < nodes.len() {
+      let stuck = in_degrees.iter().position( |degree| *degree > 0 ).unwrap();
+      return Err( CycleError { node: nodes[ stuck ] } );
+    }
+
+    let mut rank = vec![0; nodes.len()];
+    for (position, idx) in order.iter().enumerate() {
+      rank[ *idx ] = position;
+    }
+    let mut offsets = Vec::with_capacity( nodes.len() + 1 );
+    let mut edges = Vec::with_capacity( found_edges.len() );
+    offsets.push( 0 );
+    for idx in &order {
+      edges.extend( found_edges[ ranges[*idx].clone() ].iter().map( |to| rank[*to] ) );
+      offsets.push( edges.len() );
+    }
+    for idx in index.values_mut() {
+      *idx = rank[ *idx ];
+    }
+    let nodes = order.iter().map( |idx| nodes[*idx] ).collect();
+    Ok( Self { nodes, index, offsets, edges } )
+  }
+
+  pub fn len( &self ) -> usize {
+    self.nodes.len()
+  }
+
+  pub fn is_empty( &self ) -> bool {
+    self.nodes.is_empty()
+  }
+
+  pub fn contains( &self, node: N ) -> bool {
+    self.index.contains_key( &node )
+  }
+
+  /// Nodes in topological order.
+  pub fn nodes( &self ) -> &[N] {
+    &self.nodes
+  }
+
+  pub fn successors( &self, node: N ) -> impl Iterator<Item=N> + '_ {
+    self.index.get( &node ).into_iter()
+      .flat_map( |idx| self.successors_of( *idx ) )
+      .map( |idx| self.nodes[ *idx ] )
+  }
+
+  fn successors_of( &self, idx: usize ) -> &[usize] {
+    &self.edges[ self.offsets[idx]..self.offsets[idx+1] ]
+  }
+
+  /// Computes a value for every node from the values of its successors, sinks first.
+  pub fn fold<V>( &self, mut f: impl FnMut( N, Successors<'_, V> ) -> V ) -> NodeValues<'_, N, V> {
+    // values are pushed in reverse topological order, the value of node `idx` ending up at `len - 1 - idx`
+    let len = self.nodes.len();
+    let mut values = Vec::with_capacity( len );
+    for idx in (0..len).rev() {
+      let successors = Successors { indices: self.successors_of( idx ).iter(), values: &values, len };
+      let value = f( self.nodes[ idx ], successors );
+      values.push( value );
+    }
+    values.reverse();
+    NodeValues { dag: self, values }
+  }
+
+  /// Number of distinct paths from each node to any node satisfying `is_sink`.
+  pub fn count_paths( &self, is_sink: impl Fn( N ) -> bool ) -> NodeValues<'_, N, usize> {
+    self.fold( |node, successors| usize::from( is_sink(node) ) + successors.sum::<usize>() )
+  }
+
+  /// Distinct nodes satisfying `is_sink` reachable from each node, in topological order.
+  pub fn reachable_sinks( &self, is_sink: impl Fn( N ) -> bool ) -> NodeValues<'_, N, Vec<N>> {
+    let sinks = self.fold( |node, successors| {
+      let mut sinks = successors.flatten().copied().collect::<Vec<usize>>();
+      if is_sink( node ) {
+        sinks.push( self.index[ &node ] );
+      }
+      sinks.sort_unstable();
+      sinks.dedup();
+      sinks
+    });
+    NodeValues {
+      dag: self,
+      values: sinks.values.into_iter().map( |sinks| sinks.into_iter().map( |idx| self.nodes[idx] ).collect() ).collect(),
+    }
+  }
+
+  /// Number of distinct sinks, and of distinct paths to them, from each node satisfying `is_source`, in topological
+  /// order.
+  ///
+  /// One pass from the sinks up, counting paths as [`Dag::count_paths`] does while each node merges the sorted sinks
+  /// of its successors. A node's sinks are dropped as soon as its last predecessor has merged them, so only the sets
+  /// of nodes still waiting on a predecessor are alive at once rather than one set per node.
+  pub fn source_summaries( &self, is_source: impl Fn( N ) -> bool, is_sink: impl Fn( N ) -> bool ) -> Vec<SourceSummary<N>> {
+    let len = self.nodes.len();
+    let mut pending = vec![0_usize; len];
+    for to in &self.edges {
+      pending[ *to ] += 1;
+    }
+    let mut sinks = vec![Vec::new(); len];
+    let mut paths = vec![0; len];
+    let mut summaries = Vec::new();
+    for idx in (0..len).rev() {
+      let node = self.nodes[ idx ];
+      let (mut reached, mut count) = if is_sink( node ) { (vec![idx], 1) } else { (Vec::new(), 0) };
+      for next in self.successors_of( idx ) {
+        reached.extend_from_slice( &sinks[ *next ] );
+        count += paths[ *next ];
+        pending[ *next ] -= 1;
+        if pending[ *next ] == 0 {
+          sinks[ *next ] = Vec::new();
+        }
+      }
+      reached.sort_unstable();
+      reached.dedup();
+      paths[ idx ] = count;
+      if is_source( node ) {
+        summaries.push( SourceSummary { source: node, sinks: reached.len(), paths: count } );
+      }
+      if pending[ idx ] > 0 {
+        sinks[ idx ] = reached;
+      }
+    }
+    summaries.reverse();
+    summaries
+  }
+}
+
+/// Per source summary of the paths leaving it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSummary<N> {
+  pub source: N,
+  /// Number of distinct sinks reachable from the source.
+  pub sinks: usize,
+  /// Number of distinct paths from the source to any sink.
+  pub paths: usize,
+}
+
+/// Values of the successors of the node being folded.
+pub struct Successors<'a, V> {
+  indices: slice::Iter<'a, usize>,
+  values: &'a [V],
+  len: usize,
+}
+
+impl<'a, V> Iterator for Successors<'a, V> {
+  type Item = &'a V;
+
+  fn next( &mut self ) -> Option<&'a V> {
+    self.indices.next().map( |idx| &self.values[ self.len - 1 - idx ] )
+  }
+}
+
+/// One value per node of a [`Dag`].
+pub struct NodeValues<'dag, N, V> {
+  dag: &'dag Dag<N>,
+  values: Vec<V>,
+}
+
+impl<N, V> NodeValues<'_, N, V> where N: Copy + Eq + Hash {
+  pub fn get( &self, node: N ) -> Option<&V> {
+    self.dag.index.get( &node ).map( |idx| &self.values[ *idx ] )
+  }
+
+  /// Nodes and their value, in topological order.
+  pub fn iter( &self ) -> impl Iterator<Item=(N, &V)> {
+    self.dag.nodes.iter().copied().zip( &self.values )
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  //   1 → 2 → 4 → 5
+  //   ↓       ↑
+  //   3 ──────┘   6 → 5
+  fn graph() -> HashMap<u32, Vec<u32>> {
+    HashMap::from( [(1, vec![2, 3]), (2, vec![4]), (3, vec![4]), (4, vec![5]), (6, vec![5])] )
+  }
+
+  #[test]
+  fn test_topological_order() {
+    let dag = Dag::new( &graph(), [1, 6] ).unwrap();
+    assert_eq!( 6, dag.len() );
+    let rank = |node| dag.nodes().iter().position( |n| *n == node ).unwrap();
+    for node in dag.nodes() {
+      assert!( dag.successors(*node).all( |next| rank(*node) < rank(next) ) );
+    }
+    assert_eq!( vec![2, 3], { let mut next = dag.successors(1).collect::<Vec<_>>(); next.sort(); next } );
+  }
+
+  #[test]
+  fn test_cycle() {
+    let mut graph = graph();
+    graph.insert( 5, vec![2] );
+    assert!( Dag::new( &graph, [1] ).is_err() );
+    assert!( Dag::new( &graph, [6] ).is_err() );
+  }
+
+  #[test]
+  fn test_counts() {
+    let dag = Dag::new( &graph(), [1, 6] ).unwrap();
+    let paths = dag.count_paths( |node| node == 5 );
+    assert_eq!( Some(&2), paths.get(1) );
+    assert_eq!( Some(&1), paths.get(6) );
+    assert_eq!( None, paths.get(7) );
+
+    let sinks = dag.reachable_sinks( |node| node == 4 || node == 5 );
+    assert_eq!( Some(&vec![4, 5]), sinks.get(1) );
+    assert_eq!( Some(&vec![5]), sinks.get(6) );
+
+    let mut summaries = dag.source_summaries( |node| node == 1 || node == 6, |node| node == 4 || node == 5 );
+    summaries.sort_by_key( |summary| summary.source );
+    let expected = vec![SourceSummary { source: 1, sinks: 2, paths: 4 }, SourceSummary { source: 6, sinks: 1, paths: 1 }];
+    assert_eq!( expected, summaries );
+  }
+}
@@ -0,0 +1,151 @@
+use crate::{render::{Canvas, Color, Overlay}, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LocationID(usize);
+
+type Lists = (Vec<LocationID>, Vec<LocationID>);
+
+pub struct Day01;
+
+impl Solution for Day01 {
+  const DAY: u8 = 1;
+  type Input = Lists;
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+}
+
+fn parse_input( input: &str ) -> Result<Lists, ParseError> {
+  input.lines()
+    .map( |line| {
+      let mut split = line.split_whitespace();
+      let mut next = || split.next()
+        .ok_or_else( || ParseError::new( input, &line[line.len()..], "two location IDs" ) )
+        .and_then( |id| ParseError::parse( input, id, "a location ID" ) )
+        .map( LocationID );
+      Ok(( next()?, next()? ))
+    })
+    .collect()
+}
+
+/// The sorted lists side by side with the distance of each pair, in yellow when apart, left IDs also in the right list,
+/// those part two counts, in green.
+fn visualize( lists: &Lists ) -> Canvas {
+  let (left, right) = part_one::get_sorted_data( lists );
+  let width = left.iter().chain( &right ).map( |id| id.0.to_string().len() ).max().unwrap_or( 0 );
+  let text = std::iter::zip( &left, &right )
+    .map( |(left, right)| format!( "{:>width$} {:>width$} {:>width$}\n", left.0, right.0, left.0.abs_diff(right.0) ) )
+    .collect::<String>();
+  std::iter::zip( &left, &right ).enumerate()
+    .fold( Canvas::text( &text ), |canvas, (row, (left_id, right_id))| {
+      let canvas = match left_id != right_id {
+        true => canvas.overlay( &Overlay::span( row, 2*width + 2..3*width + 2, Color::Yellow ) ),
+        false => canvas,
+      };
+      match right.binary_search( left_id ).is_ok() {
+        true => canvas.overlay( &Overlay::span( row, 0..width, Color::Green ) ),
+        false => canvas,
+      }
+    })
+}
+
+// =================================================================================================================================
+
+mod part_one {
+  use super::*;
+
+  pub(super) fn compute_answer( lists: &Lists ) -> usize {
+    let (left, right) = get_sorted_data( lists );
+    std::iter::zip( left, right )
+      .map( |(left, right)| left.0.abs_diff(right.0) )
+      .sum()
+  }
+
+  pub(super) fn get_sorted_data( lists: &Lists ) -> Lists {
+    let (mut left, mut right) = lists.clone();
+    left.sort_unstable();
+    right.sort_unstable();
+    (left, right)
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_get_sorted_data() {
+      let (left, right) = get_sorted_data( &parse_input(TEST_INPUT).unwrap() );
+      println!( "{left:?}" );
+      println!( "{right:?}" );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 11;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+// =================================================================================================================================
+
+mod part_two {
+  use super::*;
+
+  pub(super) fn compute_answer( (left, right): &Lists ) -> usize {
+    let count = |id: &LocationID| {
+      right.iter()
+        .filter( |other| *other == id )
+        .count()
+    };
+
+    left.iter()
+      .map( |id| id.0 * count(id) )
+      .sum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 31;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  pub(super) const TEST_INPUT: &str =
+  "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+  #[test]
+  fn test_parse_input() {
+    let (left, right) = parse_input( TEST_INPUT ).unwrap();
+    println!( "{left:?}" );
+    println!( "{right:?}" );
+
+    let error = parse_input( "3   4\n4   x3" ).unwrap_err();
+    assert_eq!( (2, 5, "x3"), (error.line, error.column, error.text.as_str()) );
+    let error = parse_input( "3   4\n4" ).unwrap_err();
+    assert_eq!( (2, 2, ""), (error.line, error.column, error.text.as_str()) );
+  }
+}
@@ -1,19 +1,20 @@
 use std::str::FromStr;
 use itertools::Itertools;
+use crate::{parallel::*, render::{Canvas, Color, Overlay}, ParseError, Solution};
 
 type Level = i32;
 
 #[derive(Debug)]
-struct Report {
+pub struct Report {
   levels: Vec<Level>,
 }
 
 impl FromStr for Report {
-  type Err = <Level as FromStr>::Err;
+  type Err = ParseError;
 
   fn from_str( input: &str ) -> Result<Self, Self::Err> {
     let levels = input.split_whitespace()
-      .map( str::parse::<Level> )
+      .map( |level| ParseError::parse::<Level>( input, level, "a level" ) )
       .collect::<Result<_,_>>()?;
     let this = Self { levels };
     Ok( this )
@@ -57,12 +58,45 @@ impl Report {
   }
 }
 
-pub fn main() {
-  let input = include_str!( "day_02.input" );
-  let answer = part_one::compute_answer( input );
-  println!( "{answer}" );
-  let answer = part_two::compute_answer( input );
-  println!( "{answer}" );
+pub struct Day02;
+
+impl Solution for Day02 {
+  const DAY: u8 = 2;
+  type Input = Vec<Report>;
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+}
+
+fn parse_input( input: &str ) -> Result<Vec<Report>, ParseError> {
+  input.lines().enumerate()
+    .map( |(idx, line)| line.parse::<Report>().map_err( |error| error.on_line(idx+1) ) )
+    .collect()
+}
+
+/// Safe reports in green, reports the dampener makes safe in yellow with the level it removes in red, unsafe reports
+/// in red.
+fn visualize( reports: &[Report] ) -> Canvas {
+  let lines = reports.iter()
+    .map( |report| report.levels.iter().map( Level::to_string ).collect::<Vec<_>>() )
+    .collect::<Vec<_>>();
+  let text = lines.iter().map( |levels| levels.join( " " ) + "\n" ).collect::<String>();
+  std::iter::zip( reports, &lines ).enumerate()
+    .fold( Canvas::text( &text ), |canvas, (row, (report, levels))| {
+      let width = levels.iter().map( String::len ).sum::<usize>() + levels.len().saturating_sub( 1 );
+      match report.is_safe_dampened() {
+        Ok(None) => canvas.overlay( &Overlay::span( row, 0..width, Color::Green ) ),
+        Ok(Some(nth)) => {
+          let start = levels[..nth].iter().map( |level| level.len() + 1 ).sum::<usize>();
+          canvas.overlay( &Overlay::span( row, 0..width, Color::Yellow ) )
+            .overlay( &Overlay::span( row, start..start + levels[nth].len(), Color::Red ) )
+        },
+        Err(()) => canvas.overlay( &Overlay::span( row, 0..width, Color::Red ) ),
+      }
+    })
 }
 
 // =================================================================================================================================
@@ -70,10 +104,9 @@ pub fn main() {
 mod part_one {
   use super::*;
 
-  pub(super) fn compute_answer( input: &str ) -> usize {
-    input.lines()
-      .map( |line| line.parse::<Report>().unwrap() )
-      .filter( Report::is_safe )
+  pub(super) fn compute_answer( reports: &[Report] ) -> usize {
+    reports.maybe_par_iter()
+      .filter( |report| report.is_safe() )
       .count()
   }
 
@@ -94,7 +127,7 @@ mod part_one {
     #[test]
     fn test_compute_answer() {
       let expected = 2;
-      let actual = compute_answer( TEST_INPUT );
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
       assert_eq!( expected, actual );
     }
   }
@@ -103,9 +136,8 @@ mod part_one {
 mod part_two {
   use super::*;
 
-  pub(super) fn compute_answer( input: &str ) -> usize {
-    input.lines()
-      .map( |line| line.parse::<Report>().unwrap() )
+  pub(super) fn compute_answer( reports: &[Report] ) -> usize {
+    reports.maybe_par_iter()
       .filter( |report| report.is_safe_dampened().is_ok() )
       .count()
   }
@@ -127,9 +159,19 @@ mod part_two {
     #[test]
     fn test_compute_answer() {
       let expected = 4;
-      let actual = compute_answer( TEST_INPUT );
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
       assert_eq!( expected, actual );
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+      let reports = parse_input( &[TEST_INPUT; 50].join("\n") ).unwrap();
+      let serial = reports.iter()
+        .filter( |report| report.is_safe_dampened().is_ok() )
+        .count();
+      assert_eq!( serial, compute_answer( &reports ) );
+    }
   }
 }
 
@@ -151,5 +193,8 @@ mod tests {
         .expect( "line should be a valid Report" );
       println!( "{report:?}" );
     }
+
+    let error = parse_input( "7 6 4\n1 2 -x" ).unwrap_err();
+    assert_eq!( (2, 5, "-x"), (error.line, error.column, error.text.as_str()) );
   }
 }
@@ -0,0 +1,102 @@
+use crate::{render::{Canvas, Color, Overlay}, ParseError, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+  const DAY: u8 = 3;
+  type Input = String;
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { Ok( input.to_owned() ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+}
+
+/// Multiplications in green while enabled and in red once a `don't()` disables them, `do()` and `don't()` in yellow.
+fn visualize( input: &str ) -> Canvas {
+  let regex = regex::Regex::new( r"do\(\)|don't\(\)|mul\(\d{1,3},\d{1,3}\)" ).unwrap();
+  let mut enabled = true;
+  regex.find_iter( input )
+    .fold( Canvas::text( input ), |canvas, found| {
+      let color = match found.as_str() {
+        "do()" => { enabled = true; Color::Yellow },
+        "don't()" => { enabled = false; Color::Yellow },
+        _ if enabled => Color::Green,
+        _ => Color::Red,
+      };
+      let line_start = input[..found.start()].rfind( '\n' ).map_or( 0, |newline| newline + 1 );
+      let row = input[..line_start].matches( '\n' ).count();
+      let col = input[line_start..found.start()].chars().count();
+      canvas.overlay( &Overlay::span( row, col..col + found.as_str().len(), color ) )
+    })
+}
+
+mod part_one {
+  pub(super) fn compute_answer( input: &str ) -> usize {
+    let regex = regex::Regex::new( r"mul\((\d{1,3}),(\d{1,3})\)" ).unwrap();
+    regex.captures_iter( input )
+      .map( |capture| capture.extract() )
+      .map( |(_, [x,y])| x.parse::<usize>().unwrap() * y.parse::<usize>().unwrap() )
+      .sum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 161;
+      let actual = compute_answer( TEST_INPUT );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+mod part_two {
+  enum State {
+    Enabled,
+    Disabled,
+  }
+
+  pub(super) fn compute_answer( input: &str ) -> usize {
+    let regex = regex::Regex::new( r"(do\(\))|(don't\(\))|mul\((\d{1,3}),(\d{1,3})\)" ).unwrap();
+    let mut state = State::Enabled;
+    let mut sum = 0;
+    for c in regex.captures_iter( input ) {
+      match (c.get(1), c.get(2), c.get(3), c.get(4) ) {
+        (Some(_do), None, None, None) => state = State::Enabled,
+        (None, Some(_dont), None, None) => state = State::Disabled,
+        (None, None, Some(x), Some(y)) if matches!(state,State::Enabled) =>
+          sum += x.as_str().parse::<usize>().unwrap() * y.as_str().parse::<usize>().unwrap(),
+        _ => {},
+      }
+    }
+    sum
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 48;
+      let actual = compute_answer( TEST_INPUT );
+      assert_eq!( expected, actual );
+    }
+
+    #[test]
+    fn test_visualize() {
+      let canvas = super::super::visualize( TEST_INPUT );
+      assert_eq!( format!( "{TEST_INPUT}\n" ), canvas.to_string() );
+      let expected = "x\x1b[32mmul(2,4)\x1b[0m&mul[3,7]!^\x1b[33mdon't()\x1b[0m_\x1b[31mmul(5,5)\x1b[0m+mul(32,64](";
+      assert!( canvas.ansi().to_string().starts_with( expected ) );
+    }
+  }
+}
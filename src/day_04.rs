@@ -0,0 +1,154 @@
+use std::iter;
+use crate::{grid::{Direction8, Pos}, render::{Canvas, Color, Overlay}, Mat2D, ParseError, Solution};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+  const DAY: u8 = 4;
+  type Input = Mat2D<char>;
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+}
+
+fn parse_input( input: &str ) -> Result<Mat2D<char>, ParseError> {
+  Mat2D::try_from_lines( input, Some, "a letter" )
+}
+
+/// Whether `word` is read from `start` going in `direction`.
+fn spells( mat: &Mat2D<char>, start: Pos, direction: Direction8, word: &[char] ) -> bool {
+  iter::successors( Some(start), |position| position.step( direction, mat ) )
+    .take( word.len() )
+    .map( |position| mat[position] )
+    .eq( word.iter().copied() )
+}
+
+/// Letters of every XMAS in yellow, centers of every X-MAS in green.
+fn visualize( mat: &Mat2D<char> ) -> Canvas {
+  let xmas = mat.iter()
+    .flat_map( |(position, _)| part_one::words_at( mat, position.into() ) )
+    .flatten();
+  let centers = mat.iter()
+    .filter( |(position, _)| part_two::count_at( mat, Pos::from(*position) ) > 0 )
+    .map( |(position, _)| position );
+  Canvas::with_palette( mat, |_| Some(Color::Grey) )
+    .overlay( &Overlay::path( xmas ) )
+    .overlay( &Overlay::new( centers, Color::Green ) )
+}
+
+mod part_one {
+  use super::*;
+
+  const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
+
+  fn count_at( mat: &Mat2D<char>, start: Pos ) -> usize {
+    Direction8::DIRECTIONS.into_iter()
+      .filter( |direction| spells( mat, start, *direction, &XMAS ) )
+      .count()
+  }
+
+  /// Cells of each XMAS starting at `start`.
+  pub(super) fn words_at( mat: &Mat2D<char>, start: Pos ) -> impl Iterator<Item=Vec<Pos>> + '_ {
+    Direction8::DIRECTIONS.into_iter()
+      .filter( move |direction| spells( mat, start, *direction, &XMAS ) )
+      .map( move |direction| iter::successors( Some(start), |position| position.step( direction, mat ) )
+        .take( XMAS.len() )
+        .collect() )
+  }
+
+  pub(super) fn compute_answer( mat: &Mat2D<char> ) -> usize {
+    mat.iter()
+      .map( |(position, _)| count_at( mat, position.into() ) )
+      .sum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_count_at() {
+      let mat = parse_input( TEST_INPUT ).unwrap();
+      let count = count_at( &mat, Pos::new(0, 5) );
+      assert_eq!( 1, count );
+      let count = count_at( &mat, Pos::new(4, 6) );
+      assert_eq!( 2, count );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let count = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( 18, count );
+    }
+  }
+}
+
+mod part_two {
+  use super::*;
+
+  pub(super) fn count_at( mat: &Mat2D<char>, center: Pos ) -> usize {
+    const MAS: [char; 3] = ['M', 'A', 'S'];
+
+    let branch = |direction: Direction8| [direction, direction.turn_around()].into_iter()
+      .any( |direction| center.step( direction.turn_around(), mat )
+        .is_some_and( |start| spells( mat, start, direction, &MAS ) ));
+
+    let one = branch( Direction8::SouthEast );
+    let two = branch( Direction8::SouthWest );
+
+    if one & two { 1 } else { 0 }
+  }
+
+  pub(super) fn compute_answer( mat: &Mat2D<char> ) -> usize {
+    mat.iter()
+      .map( |(position, _)| count_at( mat, position.into() ) )
+      .sum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_count_at() {
+      let mat = parse_input( TEST_INPUT ).unwrap();
+      let count = count_at( &mat, Pos::new(1, 2) );
+      assert_eq!( 1, count );
+      let count = count_at( &mat, Pos::new(2, 7) );
+      assert_eq!( 1, count );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let count = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( 9, count );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  pub(super) const TEST_INPUT: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+  #[test]
+  fn test_input() {
+    let mat = parse_input( TEST_INPUT ).unwrap();
+    assert_eq!( Some(&'M'), mat.get((4, 1)) );
+  }
+}
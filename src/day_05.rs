@@ -0,0 +1,801 @@
+use std::{collections::{HashMap, HashSet}, fmt};
+use crate::{bitset::BitMatrix, render::{Canvas, Color, Overlay}, report::{json_array, BoxedReport, Report}, ParseError, Part, Solution};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+  const DAY: u8 = 5;
+  type Input = (RuleSet, Updates);
+  type Answer = MiddleSum;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer {
+    MiddleSum { sum: part_one::compute_answer( input ), ..MiddleSum::default() }
+  }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+  /// The same for both parts : the rule file's lint, then the repairs of the updates part one leaves out and part two
+  /// orders.
+  fn report( (rule_set, updates): &Self::Input, _part: Part ) -> Option<BoxedReport> {
+    let repairs = updates.iter()
+      .filter( |update| !rule_set.is_ordered( update ) )
+      .map( |update| Repair {
+        update: update.clone(),
+        violations: rule_set.violations( update ),
+        moves: rule_set.min_moves( update ),
+      } )
+      .collect();
+    Some( Box::new( RulesReport { lint: rule_set.lint( updates ), repairs: Repairs( repairs ) } ) )
+  }
+}
+
+/// Ordered updates in green, the others in yellow with the pages of the rules they break in red, updates whose rules
+/// form a cycle in magenta.
+fn visualize( (rule_set, updates): &(RuleSet, Updates) ) -> Canvas {
+  let lines = updates.iter()
+    .map( |update| update.iter().map( usize::to_string ).collect::<Vec<_>>() )
+    .collect::<Vec<_>>();
+  let text = lines.iter().map( |pages| pages.join( "," ) + "\n" ).collect::<String>();
+  std::iter::zip( updates, &lines ).enumerate()
+    .fold( Canvas::text( &text ), |canvas, (row, (update, pages))| {
+      let width = pages.iter().map( String::len ).sum::<usize>() + pages.len().saturating_sub( 1 );
+      if rule_set.is_ordered( update ) {
+        return canvas.overlay( &Overlay::span( row, 0..width, Color::Green ) );
+      }
+      if rule_set.order( update ).is_err() {
+        return canvas.overlay( &Overlay::span( row, 0..width, Color::Magenta ) );
+      }
+      let broken = rule_set.violations( update ).into_iter()
+        .flat_map( |(left, right)| [left, right] )
+        .collect::<HashSet<_>>();
+      let mut start = 0;
+      let mut canvas = canvas.overlay( &Overlay::span( row, 0..width, Color::Yellow ) );
+      for (page, text) in std::iter::zip( update, pages ) {
+        if broken.contains( page ) {
+          canvas = canvas.overlay( &Overlay::span( row, start..start + text.len(), Color::Red ) );
+        }
+        start += text.len() + 1;
+      }
+      canvas
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+  /// Rules as given, duplicates included.
+  rules: Vec<(usize, usize)>,
+  /// Pages of the rules, by index in `precedes`.
+  pages: Vec<usize>,
+  indices: HashMap<usize, usize>,
+  /// Bit (left, right) set when a rule puts the page of index left before the page of index right.
+  precedes: BitMatrix,
+}
+
+impl FromIterator<(usize, usize)> for RuleSet {
+  fn from_iter<II>( input: II ) -> Self where II: IntoIterator<Item=(usize, usize)> {
+    let rules = input.into_iter().collect::<Vec<_>>();
+    let mut pages = Vec::new();
+    let mut indices = HashMap::new();
+    for page in rules.iter().flat_map( |(left, right)| [*left, *right] ) {
+      indices.entry( page ).or_insert_with( || {
+        pages.push( page );
+        pages.len() - 1
+      } );
+    }
+    let mut precedes = BitMatrix::new( pages.len(), pages.len() );
+    for (left, right) in &rules {
+      precedes.insert( indices[left], indices[right] );
+    }
+    Self { rules, pages, indices, precedes }
+  }
+}
+
+/// The update's pages sorted so that no rule is broken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopologicalOrder {
+  pub pages: Vec<usize>,
+  /// Consecutive pages no rule orders, which could as well be swapped : the order is the only one when there's none.
+  pub ambiguities: Vec<(usize, usize)>,
+}
+
+impl TopologicalOrder {
+  pub fn is_unique( &self ) -> bool {
+    self.ambiguities.is_empty()
+  }
+}
+
+/// Rules between an update's pages going round in a circle, so that they can't be ordered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+  /// Pages of the cycle, each one having to come before the next one, and the last one before the first one.
+  pub cycle: Vec<usize>,
+}
+
+impl fmt::Display for CycleError {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    let rules = self.cycle.iter().zip( self.cycle.iter().cycle().skip(1) )
+      .map( |(left, right)| format!( "{left}|{right}" ) )
+      .collect::<Vec<_>>();
+    write!( f, "rules {} form a cycle", rules.join( ", " ) )
+  }
+}
+
+impl std::error::Error for CycleError {}
+
+impl RuleSet {
+  /// Whether a rule puts `left` before `right`.
+  pub fn precedes( &self, left: usize, right: usize ) -> bool {
+    self.precedes_indexed( self.indices.get( &left ).copied(), self.indices.get( &right ).copied() )
+  }
+
+  /// Index of each page of `update`, `None` for pages without rules, so that pairs of pages are then checked
+  /// without hashing.
+  fn indexed( &self, update: &[usize] ) -> Vec<Option<usize>> {
+    update.iter().map( |page| self.indices.get( page ).copied() ).collect()
+  }
+
+  fn precedes_indexed( &self, left: Option<usize>, right: Option<usize> ) -> bool {
+    match (left, right) {
+      (Some(left), Some(right)) => self.precedes.contains( left, right ),
+      _ => false,
+    }
+  }
+
+  /// Rule set with a rule for every order following from the rules by transitivity, through any page.
+  pub fn closure( &self ) -> Self {
+    let precedes = self.precedes.transitive_closure();
+    let rules = (0..precedes.rows())
+      .flat_map( |left| precedes.row( left ).map( move |right| (self.pages[left], self.pages[right]) ) )
+      .collect();
+    Self { rules, pages: self.pages.clone(), indices: self.indices.clone(), precedes }
+  }
+
+  /// Whether no rule puts a page of `update` after a page following it.
+  pub fn is_ordered( &self, update: &[usize] ) -> bool {
+    let update = self.indexed( update );
+    update.iter().enumerate()
+      .all( |(idx, left)| update[idx+1..].iter().all( |right| !self.precedes_indexed( *right, *left ) ) )
+  }
+
+  /// The pages of `update` sorted by the rules between them, with Kahn's algorithm : the pages no remaining page
+  /// must precede come first, the earliest in the update when there are several.
+  pub fn order( &self, update: &[usize] ) -> Result<TopologicalOrder, CycleError> {
+    let indexed = self.indexed( update );
+    let mut in_degrees = indexed.iter()
+      .map( |right| indexed.iter().filter( |left| self.precedes_indexed( **left, *right ) ).count() )
+      .collect::<Vec<_>>();
+    let mut sorted = vec![false; update.len()];
+    let mut pages = Vec::with_capacity( update.len() );
+    while let Some(next) = (0..update.len()).find( |idx| !sorted[*idx] && in_degrees[*idx] == 0 ) {
+      sorted[ next ] = true;
+      pages.push( update[next] );
+      for (idx, right) in indexed.iter().enumerate() {
+        if !sorted[idx] && self.precedes_indexed( indexed[next], *right ) {
+          in_degrees[ idx ] -= 1;
+        }
+      }
+    }
+    if pages.len() < update.len() {
+      let unsorted = (0..update.len()).filter( |idx| !sorted[*idx] ).map( |idx| update[idx] ).collect::<Vec<_>>();
+      return Err( CycleError { cycle: self.find_cycle( &unsorted ) } );
+    }
+
+    let ambiguities = pages.windows( 2 )
+      .filter( |pair| !self.precedes( pair[0], pair[1] ) )
+      .map( |pair| (pair[0], pair[1]) )
+      .collect();
+    Ok( TopologicalOrder { pages, ambiguities } )
+  }
+
+  /// A cycle among `pages`, every one of them being preceded by another one : going from page to preceding page
+  /// eventually comes back to a page already seen.
+  fn find_cycle( &self, pages: &[usize] ) -> Vec<usize> {
+    let mut path = vec![pages[0]];
+    loop {
+      let current = path[ path.len()-1 ];
+      let previous = *pages.iter().find( |page| self.precedes( **page, current ) )
+        .expect( "every unsorted page should be preceded by another one" );
+      if let Some(start) = path.iter().position( |page| *page == previous ) {
+        let mut cycle = path.split_off( start );
+        cycle.reverse();
+        return cycle;
+      }
+      path.push( previous );
+    }
+  }
+
+  /// Rules broken by `update`, `a|b` when `b` comes before `a`, by position of `b` then `a`.
+  pub fn violations( &self, update: &[usize] ) -> Vec<(usize, usize)> {
+    let indexed = &self.indexed( update );
+    (0..update.len())
+      .flat_map( |right| (right+1..update.len())
+        .filter( move |left| self.precedes_indexed( indexed[*left], indexed[right] ) )
+        .map( move |left| (update[left], update[right]) ) )
+      .collect()
+  }
+
+  /// `before[i][j]` : page `i` of the indexed update must end up before page `j`, directly or through other pages of
+  /// the update, with Warshall's algorithm.
+  fn before_within( &self, update: &[Option<usize>] ) -> Vec<Vec<bool>> {
+    let len = update.len();
+    let mut before = (0..len)
+      .map( |i| (0..len).map( |j| self.precedes_indexed( update[i], update[j] ) ).collect::<Vec<_>>() )
+      .collect::<Vec<_>>();
+    for k in 0..len {
+      for i in 0..len {
+        if before[i][k] {
+          let through = before[k].clone();
+          for (reach, through) in std::iter::zip( &mut before[i], through ) {
+            *reach |= through;
+          }
+        }
+      }
+    }
+    before
+  }
+
+  /// Fewest pages to take out of `update` and put back elsewhere to have it ordered.
+  ///
+  /// The pages left in place must be ordered among themselves, even through transitivity, so the fewest moves is the
+  /// size of the update minus its largest set of pages without such an inversion. Inversions being transitive, that
+  /// is the largest antichain of a partial order, which by Dilworth's theorem is the update's size minus the largest
+  /// matching between inverted pages : the moves are that matching's size.
+  pub fn min_moves( &self, update: &[usize] ) -> Result<usize, CycleError> {
+    self.order( update )?;
+
+    let len = update.len();
+    let before = self.before_within( &self.indexed( update ) );
+
+    // Kuhn's augmenting paths, from each page to the later ones that must precede it
+    fn augment( page: usize, before: &[Vec<bool>], seen: &mut [bool], matched: &mut [Option<usize>] ) -> bool {
+      for later in page+1..before.len() {
+        if before[later][page] && !seen[later] {
+          seen[later] = true;
+          if matched[later].is_none_or( |other| augment( other, before, seen, matched ) ) {
+            matched[later] = Some( page );
+            return true;
+          }
+        }
+      }
+      false
+    }
+    let mut matched = vec![None; len];
+    Ok( (0..len)
+      .filter( |page| augment( *page, &before, &mut vec![false; len], &mut matched ) )
+      .count() )
+  }
+
+  fn rules( &self ) -> impl Iterator<Item=(usize, usize)> + '_ {
+    self.rules.iter().copied()
+  }
+
+  /// Pages some rule puts after `page`.
+  fn after( &self, page: usize ) -> impl Iterator<Item=usize> + '_ {
+    self.indices.get( &page ).into_iter()
+      .flat_map( |index| self.precedes.row( *index ).map( |right| self.pages[right] ) )
+  }
+
+  /// Whether rules lead from `left` to `right` other than through the rules `left|right` themselves, `closure` being
+  /// this rule set's. Unless `left` is on a cycle, no path from one of its other successors back to `right` goes
+  /// through it, so the closure tells; otherwise the rules are followed.
+  fn implied( &self, closure: &Self, left: usize, right: usize ) -> bool {
+    let (Some(&left_index), Some(&right_index)) = (self.indices.get( &left ), self.indices.get( &right )) else {
+      return false;
+    };
+    if closure.precedes.contains( left_index, left_index ) {
+      return self.reaches( left, right );
+    }
+    self.precedes.row( left_index )
+      .any( |next| next != right_index && closure.precedes.contains( next, right_index ) )
+  }
+
+  /// Whether rules lead from `from` to `to`, not going through the rules `from|to` themselves.
+  fn reaches( &self, from: usize, to: usize ) -> bool {
+    let mut seen = HashSet::from( [from] );
+    let mut stack = self.after( from )
+      .filter( |next| *next != to )
+      .collect::<Vec<_>>();
+    while let Some(page) = stack.pop() {
+      if page == to {
+        return true;
+      }
+      if seen.insert( page ) {
+        stack.extend( self.after( page ) );
+      }
+    }
+    false
+  }
+
+  /// Rules given twice, implied by others, or going against others, both across the whole rule file and within the
+  /// updates.
+  ///
+  /// Across the whole file, transitivity goes through every page : a rule is implied when other rules lead from its
+  /// left page to its right one, and on a cycle when they lead back. On the puzzle's rule files, cyclic as a whole,
+  /// that is every rule, while rules only matter between pages of a same update. So within the updates, a rule is
+  /// contradictory when the rules between the pages of an update holding both of its pages order them the other way
+  /// around, and redundant when in every such update, other pages of the update lead from its left page to its right
+  /// one. Rules no update holds both pages of are neither.
+  pub fn lint( &self, updates: &[Vec<usize>] ) -> RuleLint {
+    let mut counts = HashMap::<(usize, usize), usize>::new();
+    for rule in self.rules() {
+      *counts.entry( rule ).or_default() += 1;
+    }
+    let mut contradictory = HashSet::new();
+    let mut implied = HashMap::new();
+    for update in updates {
+      let indexed = self.indexed( update );
+      let before = self.before_within( &indexed );
+      for (left, right) in (0..update.len()).flat_map( |left| (0..update.len()).map( move |right| (left, right) ) ) {
+        if !self.precedes_indexed( indexed[left], indexed[right] ) {
+          continue;
+        }
+        let rule = (update[left], update[right]);
+        if before[right][left] {
+          contradictory.insert( rule );
+        }
+        let through = (0..update.len())
+          .any( |page| page != right && self.precedes_indexed( indexed[left], indexed[page] ) && before[page][right] );
+        *implied.entry( rule ).or_insert( true ) &= through;
+      }
+    }
+
+    let closure = self.closure();
+    let mut lint = RuleLint::default();
+    for ((left, right), count) in counts {
+      if contradictory.contains( &(left, right) ) {
+        lint.contradictory.push( (left, right) );
+      }
+      else if count > 1 || implied.get( &(left, right) ) == Some(&true) {
+        lint.redundant.push( (left, right) );
+      }
+      if closure.precedes( right, left ) {
+        lint.cyclic_in_file.push( (left, right) );
+      }
+      else if count > 1 || self.implied( &closure, left, right ) {
+        lint.implied_in_file.push( (left, right) );
+      }
+    }
+    lint.redundant.sort_unstable();
+    lint.contradictory.sort_unstable();
+    lint.implied_in_file.sort_unstable();
+    lint.cyclic_in_file.sort_unstable();
+    lint
+  }
+}
+
+/// Findings about the rule file, as it applies to the updates and as a whole.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleLint {
+  /// Rules given twice, or following from other rules by transitivity within every update they apply to.
+  pub redundant: Vec<(usize, usize)>,
+  /// Rules whose pages other rules order the other way around in some update, directly or by transitivity.
+  pub contradictory: Vec<(usize, usize)>,
+  /// Rules given twice, or following from other rules by transitivity through any page.
+  pub implied_in_file: Vec<(usize, usize)>,
+  /// Rules whose pages other rules order the other way around by transitivity through any page.
+  pub cyclic_in_file: Vec<(usize, usize)>,
+}
+
+fn rules_json( rules: &[(usize, usize)] ) -> String {
+  json_array( rules.iter().map( |(left, right)| format!( "[{left}, {right}]" ) ) )
+}
+
+fn rules_text( rules: &[(usize, usize)] ) -> String {
+  rules.iter().map( |(left, right)| format!( "{left}|{right}" ) ).collect::<Vec<_>>().join( ", " )
+}
+
+impl fmt::Display for RuleLint {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    writeln!( f, "{} redundant rules: {}", self.redundant.len(), rules_text( &self.redundant ) )?;
+    writeln!( f, "{} contradictory rules: {}", self.contradictory.len(), rules_text( &self.contradictory ) )?;
+    writeln!( f, "whole file, {} implied rules: {}", self.implied_in_file.len(), rules_text( &self.implied_in_file ) )?;
+    writeln!( f, "whole file, {} rules on a cycle: {}", self.cyclic_in_file.len(), rules_text( &self.cyclic_in_file ) )
+  }
+}
+
+impl Report for RuleLint {
+  fn json( &self ) -> String {
+    format!( "{{\"redundant\": {}, \"contradictory\": {}, \"implied_in_file\": {}, \"cyclic_in_file\": {}}}",
+             rules_json( &self.redundant ), rules_json( &self.contradictory ),
+             rules_json( &self.implied_in_file ), rules_json( &self.cyclic_in_file ) )
+  }
+}
+
+/// Sum of the middle pages of updates, with the updates it can't account for : those whose rules form a cycle are
+/// left out, and those with several orders count the middle page of one of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MiddleSum {
+  pub sum: usize,
+  pub cyclic: Vec<Vec<usize>>,
+  pub ambiguous: Vec<Vec<usize>>,
+}
+
+impl fmt::Display for MiddleSum {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    let updates = |updates: &[Vec<usize>]| updates.iter()
+      .map( |update| update.iter().map( usize::to_string ).collect::<Vec<_>>().join( "," ) )
+      .collect::<Vec<_>>()
+      .join( " " );
+    let mut notes = Vec::new();
+    if !self.cyclic.is_empty() {
+      notes.push( format!( "left out, rules forming a cycle: {}", updates( &self.cyclic ) ) );
+    }
+    if !self.ambiguous.is_empty() {
+      notes.push( format!( "several orders: {}", updates( &self.ambiguous ) ) );
+    }
+    match notes.is_empty() {
+      true => write!( f, "{}", self.sum ),
+      false => write!( f, "{} ({})", self.sum, notes.join( "; " ) ),
+    }
+  }
+}
+
+/// Why an update isn't ordered, and how far it is from being so.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+  pub update: Vec<usize>,
+  pub violations: Vec<(usize, usize)>,
+  pub moves: Result<usize, CycleError>,
+}
+
+pub struct Repairs( pub Vec<Repair> );
+
+impl fmt::Display for Repairs {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    for repair in &self.0 {
+      let update = repair.update.iter().map( usize::to_string ).collect::<Vec<_>>().join( "," );
+      match &repair.moves {
+        Ok(moves) => write!( f, "{update}: {moves} move(s)" )?,
+        Err(error) => write!( f, "{update}: can't be ordered, {error}" )?,
+      }
+      writeln!( f, ", breaks {}", rules_text( &repair.violations ) )?;
+    }
+    Ok(())
+  }
+}
+
+impl Report for Repairs {
+  fn json( &self ) -> String {
+    json_array( self.0.iter()
+      .map( |repair| {
+        let update = json_array( repair.update.iter().map( usize::to_string ) );
+        let moves = match &repair.moves {
+          Ok(moves) => format!( "\"moves\": {moves}" ),
+          Err(error) => format!( "\"moves\": null, \"cycle\": {}", json_array( error.cycle.iter().map( usize::to_string ) ) ),
+        };
+        format!( "{{\"update\": {update}, \"violations\": {}, {moves}}}", rules_json( &repair.violations ) )
+      } ) )
+  }
+}
+
+/// What `aoc report` shows for day 5.
+pub struct RulesReport {
+  pub lint: RuleLint,
+  pub repairs: Repairs,
+}
+
+impl fmt::Display for RulesReport {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    write!( f, "{}", self.lint )?;
+    writeln!( f, "{} unordered updates:", self.repairs.0.len() )?;
+    write!( f, "{}", self.repairs )
+  }
+}
+
+impl Report for RulesReport {
+  fn json( &self ) -> String {
+    format!( "{{\"lint\": {}, \"repairs\": {}}}", self.lint.json(), self.repairs.json() )
+  }
+}
+
+pub type Updates = Vec<Vec<usize>>;
+
+fn parse_input( input: &str ) -> Result<(RuleSet, Updates), ParseError> {
+  let Some((rule_set, updates)) = input.split_once( "\n\n" ) else {
+    return Err( ParseError::new( input, &input[input.len()..], "a blank line between rules and updates" ) );
+  };
+  let rule_set = rule_set.lines()
+    .map( |line| parse_rule( input, line ) )
+    .collect::<Result<RuleSet,_>>()?;
+  let updates = updates.lines()
+    .map( |line| line.split(',')
+      .map( |page| ParseError::parse( input, page, "a page number" ) )
+      .collect() )
+    .collect::<Result<_,_>>()?;
+  Ok(( rule_set, updates ))
+}
+
+fn parse_rule( input: &str, line: &str ) -> Result<(usize, usize), ParseError> {
+  let Some((left, right)) = line.split_once( '|' ) else {
+    return Err( ParseError::new( input, line, "a rule 'X|Y'" ) );
+  };
+  let left = ParseError::parse( input, left, "a page number" )?;
+  let right = ParseError::parse( input, right, "a page number" )?;
+  Ok(( left, right ))
+}
+
+mod part_one {
+  use super::*;
+
+  pub(super) fn compute_answer( (rule_set, updates): &(RuleSet, Updates) ) -> usize {
+    updates.iter()
+      .filter( |update| rule_set.is_ordered( update ) )
+      .map( |update| update[ update.len()/2 ] )
+      .sum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 143;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+mod part_two {
+  use super::*;
+
+  /// Updates whose rules form a cycle can't be ordered, so they are left out of the sum, and updates with several
+  /// orders count the middle page of the one Kahn's algorithm gives : the answer lists both.
+  pub(super) fn compute_answer( (rule_set, updates): &(RuleSet, Updates) ) -> MiddleSum {
+    let mut answer = MiddleSum::default();
+    for update in updates.iter().filter( |update| !rule_set.is_ordered( update ) ) {
+      match rule_set.order( update ) {
+        Ok(order) => {
+          answer.sum += order.pages[ order.pages.len()/2 ];
+          if !order.is_unique() {
+            answer.ambiguous.push( update.clone() );
+          }
+        },
+        Err(_) => answer.cyclic.push( update.clone() ),
+      }
+    }
+    answer
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 123;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual.sum );
+      assert_eq!( "123", actual.to_string() );
+    }
+
+    #[test]
+    fn test_cyclic_update() {
+      // 1,2,3 can't be ordered, 5,4 can
+      let input = parse_input( "1|2\n2|3\n3|1\n4|5\n\n3,2,1\n5,4,6" ).unwrap();
+      let answer = compute_answer( &input );
+      assert_eq!( "5 (left out, rules forming a cycle: 3,2,1; several orders: 5,4,6)", answer.to_string() );
+      assert_eq!( (5, vec![vec![3, 2, 1]], vec![vec![5, 4, 6]]), (answer.sum, answer.cyclic, answer.ambiguous) );
+      let report = Day05::report( &input, Part::Two ).unwrap();
+      assert!( report.to_string().contains( "\n3,2,1: can't be ordered, rules " ) );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  pub(super) const TEST_INPUT: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+  #[test]
+  fn test_parse_input() {
+    let (rule_set, updates) = parse_input( TEST_INPUT ).unwrap();
+    println!( "{rule_set:?}", rule_set = rule_set.rules );
+    println!( "{updates:?}" );
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    let error = parse_input( "47|53\n97-13\n\n75,47" ).unwrap_err();
+    assert_eq!( (2, 1, "97-13"), (error.line, error.column, error.text.as_str()) );
+    let error = parse_input( "47|53\n\n75,47,x1" ).unwrap_err();
+    assert_eq!( (3, 7, "x1"), (error.line, error.column, error.text.as_str()) );
+    let error = parse_input( "47|53" ).unwrap_err();
+    assert_eq!( (1, 6), (error.line, error.column) );
+  }
+
+  #[test]
+  fn test_order() {
+    let (rule_set, updates) = parse_input( TEST_INPUT ).unwrap();
+    let orders = updates.iter()
+      .map( |update| rule_set.order( update ).unwrap() )
+      .collect::<Vec<_>>();
+    assert!( orders.iter().all( TopologicalOrder::is_unique ) );
+    assert_eq!( vec![97, 75, 47, 61, 53], orders[3].pages );
+    assert_eq!( vec![61, 29, 13], orders[4].pages );
+    assert_eq!( vec![97, 75, 47, 29, 13], orders[5].pages );
+    for (update, order) in std::iter::zip( &updates, &orders ) {
+      assert_eq!( rule_set.is_ordered( update ), *update == order.pages );
+    }
+  }
+
+  #[test]
+  fn test_ambiguous_order() {
+    let rule_set = RuleSet::from_iter( [(1, 2), (3, 2)] );
+    let order = rule_set.order( &[2, 3, 4, 1] ).unwrap();
+    assert_eq!( vec![3, 4, 1, 2], order.pages );
+    assert_eq!( vec![(3, 4), (4, 1)], order.ambiguities );
+    assert!( !order.is_unique() );
+  }
+
+  #[test]
+  fn test_cycle() {
+    // 4 is only stuck behind the cycle
+    let rule_set = RuleSet::from_iter( [(1, 2), (2, 3), (3, 1), (3, 4), (5, 1)] );
+    let error = rule_set.order( &[4, 3, 5, 2, 1] ).unwrap_err();
+    assert_eq!( vec![1, 2, 3], error.cycle.iter().cycle().skip_while( |page| **page != 1 ).take(3).copied().collect::<Vec<_>>() );
+    assert_eq!( 3, error.cycle.len() );
+    assert!( error.to_string().ends_with( "form a cycle" ) );
+
+    // intransitive rules don't make a cycle, and are still followed
+    let rule_set = RuleSet::from_iter( [(1, 2), (2, 3)] );
+    assert_eq!( vec![1, 2, 3], rule_set.order( &[3, 1, 2] ).unwrap().pages );
+    assert!( !rule_set.is_ordered( &[3, 1, 2] ) );
+    assert!( !rule_set.is_ordered( &[2, 3, 1] ) );
+  }
+
+  #[test]
+  fn test_closure() {
+    let (rule_set, _) = parse_input( TEST_INPUT ).unwrap();
+    // the example's rules are already transitive
+    assert_eq!( 21, rule_set.closure().rules().count() );
+
+    let rule_set = RuleSet::from_iter( [(1, 2), (2, 3), (3, 4), (7, 5)] );
+    let closure = rule_set.closure();
+    assert!( closure.precedes( 1, 4 ) && closure.precedes( 2, 4 ) && !closure.precedes( 4, 1 ) && !closure.precedes( 1, 5 ) );
+    assert_eq!( 7, closure.rules().count() );
+    assert!( !rule_set.precedes( 1, 4 ) && !rule_set.precedes( 100, 1 ) );
+
+    // the rules' pages are indexed, whatever their numbers
+    let rule_set = RuleSet::from_iter( [(1, 200_000), (200_000, usize::MAX)] );
+    assert!( rule_set.precedes( 1, 200_000 ) && !rule_set.precedes( 200_000, 1 ) );
+    assert!( rule_set.closure().precedes( 1, usize::MAX ) );
+    assert_eq!( vec![1, 200_000, usize::MAX], rule_set.order( &[usize::MAX, 200_000, 1] ).unwrap().pages );
+  }
+
+  #[test]
+  fn test_violations() {
+    let (rule_set, updates) = parse_input( TEST_INPUT ).unwrap();
+    assert!( rule_set.violations( &updates[0] ).is_empty() );
+    assert_eq!( vec![(97, 75)], rule_set.violations( &updates[3] ) );
+    assert_eq!( vec![(29, 13)], rule_set.violations( &updates[4] ) );
+    assert_eq!( vec![(75, 13), (29, 13), (47, 13), (47, 29)], rule_set.violations( &[97, 13, 75, 29, 47] ) );
+  }
+
+  #[test]
+  fn test_min_moves() {
+    let (rule_set, updates) = parse_input( TEST_INPUT ).unwrap();
+    let moves = updates.iter().map( |update| rule_set.min_moves( update ).unwrap() ).collect::<Vec<_>>();
+    assert_eq!( vec![0, 0, 0, 1, 1, 2], moves );
+
+    // 3 must precede 1 through 2, so only one of 1, 2 and 3 can stay, 4 being free to stay anywhere
+    let rule_set = RuleSet::from_iter( [(3, 2), (2, 1)] );
+    assert_eq!( Ok(2), rule_set.min_moves( &[1, 4, 2, 3] ) );
+    // without 2 in the update, nothing orders 1 and 3
+    assert_eq!( Ok(0), rule_set.min_moves( &[1, 4, 3] ) );
+    let rule_set = RuleSet::from_iter( [(1, 2), (2, 1)] );
+    assert!( rule_set.min_moves( &[1, 2] ).is_err() );
+  }
+
+  /// Fewest moves, trying every set of pages to leave in place.
+  fn brute_force_moves( rule_set: &RuleSet, update: &[usize] ) -> usize {
+    (0_usize..1 << update.len())
+      .filter( |kept| {
+        let kept = (0..update.len()).filter( |idx| kept & (1 << idx) != 0 ).map( |idx| update[idx] ).collect::<Vec<_>>();
+        // the kept pages must appear in the same order in some ordering of the whole update
+        let mut rules = rule_set.rules().collect::<Vec<_>>();
+        rules.extend( kept.windows( 2 ).map( |pair| (pair[0], pair[1]) ) );
+        RuleSet::from_iter( rules ).order( update ).is_ok()
+      } )
+      .map( |kept| update.len() - kept.count_ones() as usize )
+      .min()
+      .unwrap()
+  }
+
+  #[test]
+  fn test_min_moves_matches_brute_force() {
+    let rule_set = RuleSet::from_iter( [(1, 5), (5, 3), (2, 6), (6, 4), (7, 4), (3, 8)] );
+    let mut update = vec![8, 4, 3, 7, 1, 6, 5, 2];
+    for _ in 0..12 {
+      assert_eq!( brute_force_moves( &rule_set, &update ), rule_set.min_moves( &update ).unwrap(), "{update:?}" );
+      update.rotate_left( 3 );
+      update.swap( 1, 6 );
+    }
+  }
+
+  #[test]
+  fn test_lint() {
+    let (rule_set, updates) = parse_input( TEST_INPUT ).unwrap();
+    let lint = rule_set.lint( &updates );
+    assert!( lint.contradictory.is_empty() );
+    // 97|13 follows from 97|61|…|13 and 97|75|…|13 in the two updates holding both pages, 97|75 from nothing
+    assert!( lint.redundant.contains( &(97, 13) ) && !lint.redundant.contains( &(97, 75) ) );
+    // 61|29 only applies to 61,13,29, which has no page between them
+    assert!( !lint.redundant.contains( &(61, 29) ) );
+    // the example's rules order all pages, every rule but the 6 between consecutive pages following from those
+    assert_eq!( 21 - 6, lint.implied_in_file.len() );
+    assert!( lint.implied_in_file.contains( &(61, 29) ) && lint.cyclic_in_file.is_empty() );
+
+    let rule_set = RuleSet::from_iter( [(1, 2), (1, 2), (2, 3), (1, 3), (4, 5), (5, 6), (6, 4), (7, 8), (8, 7)] );
+    let lint = rule_set.lint( &[vec![1, 2, 3], vec![4, 5, 6], vec![7, 8]] );
+    assert_eq!( vec![(1, 2), (1, 3)], lint.redundant );
+    assert_eq!( vec![(4, 5), (5, 6), (6, 4), (7, 8), (8, 7)], lint.contradictory );
+
+    // the cycle 4|5|6 never shows in one update, and 1|3 doesn't always go through 2
+    let lint = rule_set.lint( &[vec![1, 2, 3], vec![3, 1], vec![4, 5], vec![6, 5], vec![4, 6]] );
+    assert_eq!( vec![(1, 2)], lint.redundant );
+    assert!( lint.contradictory.is_empty() );
+    // across the whole file they still do, as without any update
+    for lint in [lint, rule_set.lint( &[] )] {
+      assert_eq!( vec![(1, 2), (1, 3)], lint.implied_in_file );
+      assert_eq!( vec![(4, 5), (5, 6), (6, 4), (7, 8), (8, 7)], lint.cyclic_in_file );
+    }
+
+    // 1|3 only follows from 1|2|1|3, itself
+    let lint = RuleSet::from_iter( [(1, 2), (2, 1), (1, 3)] ).lint( &[] );
+    assert!( lint.implied_in_file.is_empty() );
+    assert_eq!( vec![(1, 2), (2, 1)], lint.cyclic_in_file );
+  }
+
+  #[test]
+  fn test_visualize() {
+    let input = parse_input( "1|2\n2|3\n3|1\n4|5\n\n4,5\n5,10,4\n3,2,1" ).unwrap();
+    let canvas = visualize( &input );
+    assert_eq!( "4,5   \n5,10,4\n3,2,1 \n", canvas.to_string() );
+    assert_eq!( "\x1b[32m4,5\x1b[0m   \n\x1b[31m5\x1b[33m,10,\x1b[31m4\x1b[0m\n\x1b[35m3,2,1\x1b[0m \n", canvas.ansi().to_string() );
+  }
+
+  #[test]
+  fn test_report() {
+    let input = parse_input( TEST_INPUT ).unwrap();
+    let report = Day05::report( &input, Part::Two ).unwrap();
+    assert_eq!( report.to_string(), Day05::report( &input, Part::One ).unwrap().to_string() );
+    assert!( report.to_string().starts_with( "11 redundant rules: " ) );
+    assert!( report.to_string().ends_with( "3 unordered updates:\n\
+                 75,97,47,61,53: 1 move(s), breaks 97|75\n61,13,29: 1 move(s), breaks 29|13\n\
+                 97,13,75,29,47: 2 move(s), breaks 75|13, 29|13, 47|13, 47|29\n" ) );
+    assert!( report.json().starts_with( r#"{"lint": {"redundant": [[47, 13], "# ) );
+    assert!( report.json().contains( r#""cyclic_in_file": []}, "repairs": [{"update": [75, 97, 47, 61, 53], "violations": [[97, 75]], "moves": 1}, "# ) );
+  }
+}
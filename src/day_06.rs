@@ -0,0 +1,538 @@
+use std::{collections::HashSet, fmt};
+use crate::{bitset::BitSet, grid::{Direction, Pos}, render::{Canvas, Color, Overlay}, report::{json_array, BoxedReport, Report},
+            trace::{Recorder, Trace}, Mat2D, ParseError, Part, Solution};
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+pub struct Day06;
+
+impl Solution for Day06 {
+  const DAY: u8 = 6;
+  /// The map, and the guard's start position.
+  type Input = (Mat2D<char>, Pos);
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( (mat, start): &Self::Input ) -> Self::Answer { part_one::compute_answer( mat, *start ) }
+  fn part_two( (mat, start): &Self::Input ) -> Self::Answer { part_two::compute_answer( mat, *start ) }
+  fn visualize( (mat, start): &Self::Input ) -> Option<Canvas> { Some( visualize( mat, *start ) ) }
+  fn trace( (mat, start): &Self::Input, part: Part, trace: &mut Trace<String> ) -> bool {
+    match part {
+      Part::One => { patrol( mat, *start, None, trace ); true },
+      Part::Two => false,
+    }
+  }
+  fn report( (mat, start): &Self::Input, part: Part ) -> Option<BoxedReport> {
+    match part {
+      Part::One => Some( Box::new( patrol( mat, *start, None, &mut () ) ) ),
+      Part::Two => Some( Box::new( LoopingObstructions( part_two::looping_obstructions( mat, *start ) ) ) ),
+    }
+  }
+}
+
+fn parse_input( input: &str ) -> Result<(Mat2D<char>, Pos), ParseError> {
+  let mat = Mat2D::try_from_lines( input, |char| matches!( char, '.' | '#' | '^' ).then_some( char ), "'.', '#' or '^'" )?;
+  let mut guards = input.match_indices( '^' ).map( |(idx, guard)| &input[idx..idx+guard.len()] );
+  match (guards.next(), guards.next()) {
+    (Some(_), None) => (),
+    (None, _) => {
+      let end = input.trim_end().len();
+      return Err( ParseError::new( input, &input[end..end], "a single guard '^'" ) );
+    },
+    (Some(_), Some(second)) => return Err( ParseError::new( input, second, "a single guard '^'" ) ),
+  }
+  let start = find_start( &mat ).expect( "the map should have the guard the input has" );
+  Ok( (mat, start) )
+}
+
+fn find_start( mat: &Mat2D<char> ) -> Option<Pos> {
+  mat.iter()
+    .find_map( |(position, cell)|
+      (*cell == '^').then_some( Pos::from(position) )
+    )
+}
+
+/// Cells patrolled by the guard in blue, obstructions in grey, the start as a red '^'.
+fn visualize( mat: &Mat2D<char>, start: Pos ) -> Canvas {
+  Canvas::with_palette( mat, |cell| (*cell == '#').then_some( Color::Grey ) )
+    .overlay( &Overlay::visited( patrol( mat, start, None, &mut () ).visited() ).with_glyph( 'X' ) )
+    .overlay( &Overlay::marker( start, '^' ) )
+}
+
+fn arrow( direction: Direction ) -> char {
+  match direction {
+    Direction::North => '^',
+    Direction::East => '>',
+    Direction::South => 'v',
+    Direction::West => '<',
+  }
+}
+
+/// The map with the visited cells and the guard facing `direction`.
+fn snapshot( mat: &Mat2D<char>, visited: impl Iterator<Item=Pos>, guard: Pos, direction: Direction ) -> String {
+  let mat = mat.map( |cell| if *cell == '^' { '.' } else { *cell } );
+  Canvas::new( &mat )
+    .overlay( &Overlay::visited( visited ).with_glyph( 'X' ) )
+    .overlay( &Overlay::marker( guard, arrow(direction) ) )
+    .to_string()
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// The guard's state at one tick : where it stands and where it faces. Turning in place is a step of its own.
+pub type Step = (Pos, Direction);
+
+/// How a patrol ends, with every step the guard took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatrolOutcome {
+  /// The guard walked off the map from `exit_cell`, the last cell of `path`.
+  Exited { path: Vec<Step>, exit_cell: Pos },
+  /// After the steps of `prefix`, the guard repeats the steps of `cycle` forever.
+  Looped { prefix: Vec<Step>, cycle: Vec<Step> },
+}
+
+impl PatrolOutcome {
+  pub fn is_loop( &self ) -> bool {
+    matches!( self, Self::Looped { .. } )
+  }
+
+  /// Every step, the ones of a cycle once.
+  pub fn steps( &self ) -> impl Iterator<Item=&Step> {
+    let (head, tail): (&[Step], &[Step]) = match self {
+      Self::Exited { path, .. } => (path, &[]),
+      Self::Looped { prefix, cycle } => (prefix, cycle),
+    };
+    head.iter().chain( tail )
+  }
+
+  /// Distinct cells the guard goes through.
+  pub fn visited( &self ) -> HashSet<Pos> {
+    self.steps().map( |(position, _)| *position ).collect()
+  }
+}
+
+fn write_steps( f: &mut fmt::Formatter<'_>, steps: &[Step] ) -> fmt::Result {
+  for (position, direction) in steps {
+    writeln!( f, "  {},{} {}", position.row, position.col, arrow(*direction) )?;
+  }
+  Ok(())
+}
+
+fn steps_json( steps: &[Step] ) -> String {
+  json_array( steps.iter()
+    .map( |(position, direction)| format!( "{{\"row\": {}, \"col\": {}, \"direction\": \"{direction:?}\"}}", position.row, position.col ) ) )
+}
+
+impl fmt::Display for PatrolOutcome {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    match self {
+      Self::Exited { path, exit_cell } => {
+        writeln!( f, "exited at {},{} after {} steps through {} cells", exit_cell.row, exit_cell.col, path.len(), self.visited().len() )?;
+        write_steps( f, path )
+      },
+      Self::Looped { prefix, cycle } => {
+        writeln!( f, "looped after {} steps into a cycle of {} steps through {} cells", prefix.len(), cycle.len(), self.visited().len() )?;
+        writeln!( f, "prefix:" )?;
+        write_steps( f, prefix )?;
+        writeln!( f, "cycle:" )?;
+        write_steps( f, cycle )
+      },
+    }
+  }
+}
+
+impl Report for PatrolOutcome {
+  fn json( &self ) -> String {
+    match self {
+      Self::Exited { path, exit_cell } =>
+        format!( "{{\"outcome\": \"exited\", \"exit_cell\": [{}, {}], \"cells\": {}, \"path\": {}}}",
+          exit_cell.row, exit_cell.col, self.visited().len(), steps_json(path) ),
+      Self::Looped { prefix, cycle } =>
+        format!( "{{\"outcome\": \"looped\", \"cells\": {}, \"prefix\": {}, \"cycle\": {}}}",
+          self.visited().len(), steps_json(prefix), steps_json(cycle) ),
+    }
+  }
+}
+
+/// Part two's details : each obstruction trapping the guard, with the loop it makes.
+pub struct LoopingObstructions( pub Vec<(Pos, PatrolOutcome)> );
+
+impl fmt::Display for LoopingObstructions {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    writeln!( f, "{} obstructions trap the guard", self.0.len() )?;
+    for (obstruction, outcome) in &self.0 {
+      if let PatrolOutcome::Looped { prefix, cycle } = outcome {
+        writeln!( f, "  {},{}: loops after {} steps, cycle of {} steps", obstruction.row, obstruction.col, prefix.len(), cycle.len() )?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl Report for LoopingObstructions {
+  fn json( &self ) -> String {
+    json_array( self.0.iter()
+      .map( |(obstruction, outcome)| format!( "{{\"obstruction\": [{}, {}], \"outcome\": {}}}", obstruction.row, obstruction.col, outcome.json() ) ) )
+  }
+}
+
+/// Step by step patrol from `start` facing north, with an optional extra obstruction, each turn and the end being recorded.
+fn patrol( mat: &Mat2D<char>, start: Pos, obstruction: Option<Pos>, recorder: &mut impl Recorder<String> ) -> PatrolOutcome {
+  let index = |(position, direction): Step| (position.row * mat.cols() + position.col) * 4 + direction as usize;
+  let visited = |path: &[Step]| path.iter().map( |(position, _)| *position ).collect::<Vec<_>>().into_iter();
+
+  let mut seen = BitSet::new( mat.rows() * mat.cols() * 4 );
+  let mut path = Vec::new();
+  let (mut position, mut direction) = (start, Direction::North);
+  loop {
+    if !seen.insert( index( (position, direction) ) ) {
+      recorder.record( || (format!( "loop back to {},{} facing {direction:?} after {} steps", position.row, position.col, path.len() ),
+                           snapshot( mat, visited(&path), position, direction )) );
+      let first = path.iter().position( |step| *step == (position, direction) )
+        .expect( "a seen step should be in the path" );
+      let cycle = path.split_off( first );
+      return PatrolOutcome::Looped { prefix: path, cycle };
+    }
+    path.push( (position, direction) );
+
+    match position.step( direction, mat ) {
+      None => {
+        recorder.record( || (format!( "exit at {},{} after {} cells", position.row, position.col, visited(&path).collect::<HashSet<_>>().len() ),
+                             snapshot( mat, visited(&path), position, direction )) );
+        return PatrolOutcome::Exited { path, exit_cell: position };
+      },
+      Some(next) if mat[next] == '#' || obstruction == Some(next) => {
+        direction = direction.turn_right();
+        recorder.record( || (format!( "turn {direction:?} at {},{}", position.row, position.col ),
+                             snapshot( mat, visited(&path), position, direction )) );
+      },
+      Some(next) => position = next,
+    }
+  }
+}
+
+mod part_one {
+  use super::*;
+
+  pub(super) fn compute_answer( mat: &Mat2D<char>, start: Pos ) -> usize {
+    patrol( mat, start, None, &mut () ).visited().len()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 41;
+      let (mat, start) = parse_input( TEST_INPUT ).unwrap();
+      let actual = compute_answer( &mat, start );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+mod part_two {
+  use crate::parallel::*;
+  use super::*;
+
+  pub(super) fn compute_answer( mat: &Mat2D<char>, start: Pos ) -> usize {
+    let jumps = JumpTable::new( mat );
+    obstructions( mat, start ).maybe_par_iter()
+      .filter( |obstruction| is_loop( &jumps, start, **obstruction ) )
+      .count()
+  }
+
+  /// Obstructions trapping the guard, with the loop each of them makes : the jumps select them, a step by step
+  /// patrol then details their loop.
+  pub(super) fn looping_obstructions( mat: &Mat2D<char>, start: Pos ) -> Vec<(Pos, PatrolOutcome)> {
+    let jumps = JumpTable::new( mat );
+    obstructions( mat, start ).maybe_par_iter()
+      .filter( |obstruction| is_loop( &jumps, start, **obstruction ) )
+      .map( |obstruction| (*obstruction, patrol( mat, start, Some(*obstruction), &mut () )) )
+      .collect()
+  }
+
+  /// Cells where a new obstruction could change the patrol : the ones it goes through, but the start.
+  fn obstructions( mat: &Mat2D<char>, start: Pos ) -> Vec<Pos> {
+    let mut obstructions = patrol( mat, start, None, &mut () ).visited().into_iter()
+      .filter( |position| *position != start )
+      .collect::<Vec<_>>();
+    obstructions.sort_unstable();
+    obstructions
+  }
+
+  /// For each cell and direction, the cell where the guard stops in front of the next obstruction,
+  /// `None` when it walks off the map instead.
+  pub(super) struct JumpTable {
+    rows: usize,
+    cols: usize,
+    stops: Vec<Option<Pos>>,
+  }
+
+  impl JumpTable {
+    pub(super) fn new( mat: &Mat2D<char> ) -> Self {
+      let (rows, cols) = (mat.rows(), mat.cols());
+      let mut table = Self { rows, cols, stops: vec![None; rows * cols * 4] };
+      for row in 0..rows {
+        table.sweep( Direction::West, (0..cols).map( |col| Pos::new(row, col) ), mat );
+        table.sweep( Direction::East, (0..cols).rev().map( |col| Pos::new(row, col) ), mat );
+      }
+      for col in 0..cols {
+        table.sweep( Direction::North, (0..rows).map( |row| Pos::new(row, col) ), mat );
+        table.sweep( Direction::South, (0..rows).rev().map( |row| Pos::new(row, col) ), mat );
+      }
+      table
+    }
+
+    /// Fills the stops going in `direction` along a line of cells, given from the edge the guard walks toward.
+    fn sweep( &mut self, direction: Direction, line: impl Iterator<Item=Pos>, mat: &Mat2D<char> ) {
+      let mut stop = None;
+      for position in line {
+        if mat[position] == '#' {
+          stop = position.offset( direction.turn_around() );
+        }
+        else {
+          let idx = self.index( position, direction );
+          self.stops[ idx ] = stop;
+        }
+      }
+    }
+
+    fn index( &self, position: Pos, direction: Direction ) -> usize {
+      (position.row * self.cols + position.col) * 4 + direction as usize
+    }
+
+    fn states( &self ) -> usize {
+      self.rows * self.cols * 4
+    }
+
+    /// Where the guard stops walking from `from` toward `direction`, with one more obstruction on the map.
+    pub(super) fn jump( &self, from: Pos, direction: Direction, obstruction: Pos ) -> Option<Pos> {
+      let stop = self.stops[ self.index( from, direction ) ];
+      let blocked = match direction {
+        Direction::North => obstruction.col == from.col && obstruction.row < from.row
+          && stop.is_none_or( |stop| obstruction.row >= stop.row ),
+        Direction::South => obstruction.col == from.col && obstruction.row > from.row
+          && stop.is_none_or( |stop| obstruction.row <= stop.row ),
+        Direction::West => obstruction.row == from.row && obstruction.col < from.col
+          && stop.is_none_or( |stop| obstruction.col >= stop.col ),
+        Direction::East => obstruction.row == from.row && obstruction.col > from.col
+          && stop.is_none_or( |stop| obstruction.col <= stop.col ),
+      };
+      match blocked {
+        true => obstruction.offset( direction.turn_around() ),
+        false => stop,
+      }
+    }
+  }
+
+  /// Whether adding `obstruction` traps the guard in a loop, jumping from stop to stop and
+  /// remembering the (cell, direction) states it turned in.
+  pub(super) fn is_loop( jumps: &JumpTable, start: Pos, obstruction: Pos ) -> bool {
+    let mut turns = BitSet::new( jumps.states() );
+    let (mut position, mut direction) = (start, Direction::North);
+    while let Some(stop) = jumps.jump( position, direction, obstruction ) {
+      position = stop;
+      direction = direction.turn_right();
+      if !turns.insert( jumps.index( position, direction ) ) {
+        return true;
+      }
+    }
+    false
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_is_loop() {
+      let (mat, start) = parse_input( TEST_INPUT ).unwrap();
+      let jumps = JumpTable::new( &mat );
+      assert!( is_loop( &jumps, start, Pos::new(6, 3) ) );
+      assert!( !is_loop( &jumps, start, Pos::new(1, 4) ) );
+    }
+
+    #[test]
+    fn test_jump() {
+      let (mat, _) = parse_input( TEST_INPUT ).unwrap();
+      let jumps = JumpTable::new( &mat );
+      let nowhere = Pos::new( 100, 100 );
+      assert_eq!( Some(Pos::new(1, 4)), jumps.jump( Pos::new(6, 4), Direction::North, nowhere ) );
+      assert_eq!( Some(Pos::new(1, 8)), jumps.jump( Pos::new(1, 4), Direction::East, nowhere ) );
+      assert_eq!( None, jumps.jump( Pos::new(8, 7), Direction::South, nowhere ) );
+      assert_eq!( Some(Pos::new(4, 4)), jumps.jump( Pos::new(6, 4), Direction::North, Pos::new(3, 4) ) );
+      assert_eq!( Some(Pos::new(1, 4)), jumps.jump( Pos::new(6, 4), Direction::North, Pos::new(7, 4) ) );
+      assert_eq!( Some(Pos::new(8, 7)), jumps.jump( Pos::new(8, 7), Direction::South, Pos::new(9, 7) ) );
+    }
+
+    #[test]
+    fn test_jumps_match_walk() {
+      let mut tested = 0;
+      for seed in 1..=20 {
+        let mat = super::super::tests::generated_map( 30, 30, seed );
+        let start = find_start( &mat ).unwrap();
+        if patrol( &mat, start, None, &mut () ).is_loop() {
+          // the patrol itself never ends
+          continue;
+        }
+        let jumps = JumpTable::new( &mat );
+        for obstruction in obstructions( &mat, start ) {
+          assert_eq!( patrol( &mat, start, Some(obstruction), &mut () ).is_loop(), is_loop( &jumps, start, obstruction ), "seed {seed}, {obstruction:?}" );
+        }
+        tested += 1;
+      }
+      assert!( tested >= 5 );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 6;
+      let (mat, start) = parse_input( TEST_INPUT ).unwrap();
+      let actual = compute_answer( &mat, start );
+      assert_eq!( expected, actual );
+    }
+
+    #[test]
+    fn test_looping_obstructions() {
+      let (mat, start) = parse_input( TEST_INPUT ).unwrap();
+      let loops = looping_obstructions( &mat, start );
+      assert_eq!( compute_answer( &mat, start ), loops.len() );
+      assert!( loops.iter().all( |(_, outcome)| outcome.is_loop() ) );
+      assert_eq!( Pos::new(6, 3), loops[0].0 );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+      let mat = super::super::tests::generated_map( 40, 40, 7 );
+      let start = find_start( &mat ).unwrap();
+      let jumps = JumpTable::new( &mat );
+      let serial = obstructions( &mat, start ).iter()
+        .filter( |obstruction| is_loop( &jumps, start, **obstruction ) )
+        .count();
+      assert!( serial > 0 );
+      assert_eq!( serial, compute_answer( &mat, start ) );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  pub(super) const TEST_INPUT: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+  /// Pseudo-random map with about one obstruction every 8 cells and the guard in the center.
+  pub(super) fn generated_map( rows: usize, cols: usize, seed: u64 ) -> Mat2D<char> {
+    let mut state = seed;
+    let mut mat = Mat2D::new( rows, cols, '.' );
+    for (_, cell) in mat.iter_mut() {
+      state = state.wrapping_mul( 6364136223846793005 ).wrapping_add( 1442695040888963407 );
+      if (state >> 33).is_multiple_of( 8 ) {
+        *cell = '#';
+      }
+    }
+    mat[(rows/2, cols/2)] = '^';
+    mat
+  }
+
+  #[test]
+  fn test_parse_input() {
+    let (mat, start) = parse_input( TEST_INPUT ).unwrap();
+    println!( "{}", Canvas::new(&mat) );
+    assert_eq!( Pos::new(6, 4), start );
+  }
+
+  #[test]
+  fn test_trace() {
+    let mut trace = Trace::new();
+    assert!( Day06::trace( &parse_input(TEST_INPUT).unwrap(), Part::One, &mut trace ) );
+    let events = trace.frames().iter().map( |frame| frame.event.as_str() ).collect::<Vec<_>>();
+    assert_eq!( "turn East at 1,4", events[0] );
+    assert_eq!( "exit at 9,7 after 41 cells", events[events.len()-1] );
+    assert_eq!( 11, events.len() );
+    assert!( trace.frames()[0].state.starts_with( "....#.....\n....>....#\n....X....." ) );
+  }
+
+  #[test]
+  fn test_patrol() {
+    let (mat, start) = parse_input( TEST_INPUT ).unwrap();
+    let outcome = patrol( &mat, start, None, &mut () );
+    let PatrolOutcome::Exited { path, exit_cell } = &outcome else { panic!( "the guard should exit" ) };
+    assert_eq!( (Pos::new(9, 7), 41), (*exit_cell, outcome.visited().len()) );
+    assert_eq!( (start, Direction::North), path[0] );
+    assert_eq!( (Pos::new(1, 4), Direction::East), path[6] );
+
+    let outcome = patrol( &mat, start, Some(Pos::new(6, 3)), &mut () );
+    let PatrolOutcome::Looped { prefix, cycle } = &outcome else { panic!( "the guard should loop" ) };
+    // the start itself is on the loop
+    assert!( prefix.is_empty() );
+    assert_eq!( (start, Direction::North), cycle[0] );
+    // stepping on from the end of the cycle comes back to its start
+    let (last, direction) = cycle[cycle.len()-1];
+    assert_eq!( (last, direction.turn_right()), cycle[0] );
+  }
+
+  #[test]
+  fn test_report() {
+    let input = parse_input( TEST_INPUT ).unwrap();
+    let report = Day06::report( &input, Part::One ).unwrap();
+    assert!( report.to_string().starts_with( "exited at 9,7 after 55 steps through 41 cells\n  6,4 ^\n  5,4 ^\n" ) );
+    assert!( report.json().starts_with( r#"{"outcome": "exited", "exit_cell": [9, 7], "cells": 41, "path": [{"row": 6, "col": 4, "direction": "North"}, "# ) );
+
+    let report = Day06::report( &input, Part::Two ).unwrap();
+    assert!( report.to_string().starts_with( "6 obstructions trap the guard\n  6,3: loops after 0 steps, cycle of 22 steps\n" ) );
+    assert!( report.json().starts_with( r#"[{"obstruction": [6, 3], "outcome": {"outcome": "looped", "# ) );
+  }
+
+  #[test]
+  fn test_visualize() {
+    let expected = "....#.....
+....XXXXX#
+....X...X.
+..#.X...X.
+..XXXXX#X.
+..X.X.X.X.
+.#XX^XXXX.
+.XXXXXXX#.
+#XXXXXXX..
+......#X..
+";
+    let (mat, start) = parse_input( TEST_INPUT ).unwrap();
+    let actual = visualize( &mat, start ).to_string();
+    assert_eq!( expected, actual );
+  }
+
+  #[test]
+  fn test_parse_error() {
+    let error = parse_input( "..#.\n.^.x" ).unwrap_err();
+    assert_eq!( (2, 4, "x"), (error.line, error.column, error.text.as_str()) );
+
+    // exactly one guard
+    let error = parse_input( "..#.\n...." ).unwrap_err();
+    assert_eq!( ("", "a single guard '^'"), (error.text.as_str(), error.expected.as_str()) );
+    let error = parse_input( "..^.\n.^.." ).unwrap_err();
+    assert_eq!( (2, 2, "^"), (error.line, error.column, error.text.as_str()) );
+  }
+
+  #[test]
+  fn test_find_start() {
+    let (mat, _) = parse_input( TEST_INPUT ).unwrap();
+    let expected = Some( Pos::new(6, 4) );
+    let actual = find_start( &mat );
+    assert_eq!( expected, actual );
+  }
+}
@@ -0,0 +1,826 @@
+use std::{collections::HashMap, fmt, str};
+use crate::{parallel::*, render::{Canvas, Color, Overlay}, report::{json_array, json_string, BoxedReport, Report}, ParseError, Part, Solution};
+
+#[derive(Debug)]
+pub struct Equation {
+  result: isize,
+  values: Vec<isize>,
+}
+
+impl str::FromStr for Equation {
+  type Err = ParseError;
+
+  fn from_str( input: &str ) -> Result<Self, Self::Err> {
+    let Some((result, values)) = input.split_once( ':' ) else {
+      return Err( ParseError::new( input, input, "an equation 'result: values'" ) );
+    };
+    let result = ParseError::parse( input, result, "the equation's result" )?;
+    let values = values.trim().split( ' ' )
+      .map( |value| ParseError::parse( input, value, "an equation's value" ) )
+      .collect::<Result<_,_>>()?;
+
+    let this = Self { result, values };
+    Ok( this )
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+pub struct Day07;
+
+impl Solution for Day07 {
+  const DAY: u8 = 7;
+  type Input = Vec<Equation>;
+  type Answer = isize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+  fn report( input: &Self::Input, part: Part ) -> Option<BoxedReport> {
+    let solver = match part {
+      Part::One => part_one::SOLVER,
+      Part::Two => part_two::SOLVER,
+    };
+    let witnesses = input.iter()
+      .map( |equation| (equation.result, solver.solve( equation )) )
+      .collect();
+    Some( Box::new( Witnesses(witnesses) ) )
+  }
+}
+
+fn parse_input( input: &str ) -> Result<Vec<Equation>, ParseError> {
+  input.lines().enumerate()
+    .map( |(idx, line)| line.parse().map_err( |error: ParseError| error.on_line(idx+1) ) )
+    .collect()
+}
+
+/// Each equation with the expression making it true, in green when part one solves it, in yellow when only part two
+/// does, in red when neither does.
+fn visualize( equations: &[Equation] ) -> Canvas {
+  let lines = equations.iter()
+    .map( |equation| match part_two::SOLVER.solve( equation ) {
+      Some(witness) => (format!( "{} = {witness}", equation.result ), true),
+      None => (format!( "{}: no solution", equation.result ), false),
+    } )
+    .collect::<Vec<_>>();
+  let text = lines.iter().map( |(line, _)| format!( "{line}\n" ) ).collect::<String>();
+  std::iter::zip( equations, &lines ).enumerate()
+    .fold( Canvas::text( &text ), |canvas, (row, (equation, (line, solved)))| {
+      let color = match (solved, part_one::SOLVER.solve( equation ).is_some()) {
+        (_, true) => Color::Green,
+        (true, false) => Color::Yellow,
+        (false, false) => Color::Red,
+      };
+      canvas.overlay( &Overlay::span( row, 0..line.len(), color ) )
+    })
+}
+
+/// Sum of the results of the equations `solver` can make true.
+fn total_calibration( equations: &[Equation], solver: Solver<'_> ) -> isize {
+  equations.maybe_par_iter()
+    .filter( |equation| solver.solve( equation ).is_some() )
+    .map( |equation| equation.result )
+    .sum()
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// A binary operator placed between two values of an equation, displayed as its symbol.
+pub trait Operator: fmt::Display + fmt::Debug + Sync {
+  /// `left <op> right`, `None` when undefined or on overflow.
+  fn apply( &self, left: isize, right: isize ) -> Option<isize>;
+
+  /// Binding strength when evaluating with precedence, higher binding tighter.
+  fn precedence( &self ) -> u8;
+
+  fn right_associative( &self ) -> bool { false }
+
+  /// Whether positive operands always give a positive result.
+  fn keeps_positive( &self ) -> bool { false }
+
+  /// Whether `left <op> right` is never less than `left` with positive operands.
+  fn never_decreases( &self ) -> bool { false }
+
+  /// Whether, with a positive right operand and result, `undo` finds the only positive left operand giving that result,
+  /// letting equations be solved right to left.
+  fn invertible( &self ) -> bool { false }
+
+  /// The `left` such that `left <op> right == result`, if any. Only called on invertible operators.
+  fn undo( &self, _result: isize, _right: isize ) -> Option<isize> { None }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+  /// Operators applied in order, ignoring precedence, as the puzzle does.
+  LeftToRight,
+  /// Operators applied by precedence, then by associativity.
+  Precedence,
+}
+
+/// Values and operators evaluated so far : the operators still waiting for their right operand, as indices in a list
+/// of operators, each with its left operand, and the last value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Partial {
+  pending: Vec<(isize, usize)>,
+  last: isize,
+}
+
+impl Partial {
+  fn new( first: isize ) -> Self {
+    Self { pending: Vec::new(), last: first }
+  }
+
+  /// Appends `<operators[operator]> value`, applying the operators already known to bind at least as tight.
+  fn push( &mut self, operators: &[&dyn Operator], operator: usize, value: isize, mode: Mode ) -> Option<()> {
+    if mode == Mode::LeftToRight {
+      self.last = operators[ operator ].apply( self.last, value )?;
+      return Some(());
+    }
+    let precedence = operators[ operator ].precedence();
+    while let Some((left, pending)) = self.pending.last() {
+      let pending = operators[ *pending ];
+      let reduce = pending.precedence() > precedence
+        || (pending.precedence() == precedence && !operators[ operator ].right_associative());
+      if !reduce {
+        break;
+      }
+      self.last = pending.apply( *left, self.last )?;
+      self.pending.pop();
+    }
+    self.pending.push( (self.last, operator) );
+    self.last = value;
+    Some(())
+  }
+
+  fn finish( mut self, operators: &[&dyn Operator] ) -> Option<isize> {
+    while let Some((left, operator)) = self.pending.pop() {
+      self.last = operators[ operator ].apply( left, self.last )?;
+    }
+    Some( self.last )
+  }
+}
+
+/// Value of `values` joined by `operators`, `None` when an operation is undefined or overflows.
+pub fn evaluate( values: &[isize], operators: &[&dyn Operator], mode: Mode ) -> Option<isize> {
+  let mut partial = Partial::new( values[0] );
+  for (idx, value) in values[1..].iter().enumerate() {
+    partial.push( operators, idx, *value, mode )?;
+  }
+  partial.finish( operators )
+}
+
+/// Operators making an equation true, displayed as the expression they make, e.g. `81 * 40 + 27`.
+#[derive(Debug, Clone)]
+pub struct Witness<'a> {
+  pub values: Vec<isize>,
+  pub operators: Vec<&'a dyn Operator>,
+  pub mode: Mode,
+}
+
+impl Witness<'_> {
+  pub fn evaluate( &self ) -> Option<isize> {
+    evaluate( &self.values, &self.operators, self.mode )
+  }
+}
+
+/// Left to right, the expression so far is parenthesized where precedence would otherwise apply an operator first,
+/// e.g. `(81 + 40) * 27`.
+impl fmt::Display for Witness<'_> {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    let mut expression = self.values[0].to_string();
+    let mut outermost: Option<&dyn Operator> = None;
+    for (value, operator) in std::iter::zip( &self.values[1..], &self.operators ) {
+      let parenthesize = self.mode == Mode::LeftToRight && outermost.is_some_and( |outermost|
+        outermost.precedence() < operator.precedence()
+          || (outermost.precedence() == operator.precedence() && operator.right_associative()) );
+      if parenthesize {
+        expression = format!( "({expression})" );
+      }
+      expression = format!( "{expression} {operator} {value}" );
+      outermost = Some( *operator );
+    }
+    write!( f, "{expression}" )
+  }
+}
+
+/// Solves equations with a set of operators, evaluated in a given mode.
+#[derive(Debug, Clone, Copy)]
+pub struct Solver<'a> {
+  pub operators: &'a [&'a dyn Operator],
+  pub mode: Mode,
+}
+
+impl<'a> Solver<'a> {
+  pub const fn new( operators: &'a [&'a dyn Operator], mode: Mode ) -> Self {
+    Self { operators, mode }
+  }
+
+  /// Operators making `equation` true, if any.
+  ///
+  /// When [`goes_backward`](Self::goes_backward), works backward from the result, undoing the last operator :
+  /// operators which can't be undone prune their whole branch. Otherwise tries the operators value after value.
+  pub fn solve( &self, equation: &Equation ) -> Option<Witness<'a>> {
+    let operators = match self.goes_backward( equation ) {
+      true => self.backward( equation ),
+      false => self.forward( equation ),
+    }?;
+    Some( Witness { values: equation.values.clone(), operators, mode: self.mode } )
+  }
+
+  /// Whether undoing operators finds every way to make `equation` true : left to right, with positive values and
+  /// invertible operators keeping them positive. Every partial value is then positive, and only one positive left
+  /// operand gives it. A zero or negative partial value may have several, e.g. anything times zero, or come from a
+  /// partial value `Sub` took below zero, so undoing would miss some.
+  pub fn goes_backward( &self, equation: &Equation ) -> bool {
+    self.mode == Mode::LeftToRight
+      && self.operators.iter().all( |operator| operator.invertible() && operator.keeps_positive() )
+      && equation.values.iter().all( |value| *value > 0 )
+  }
+
+  fn backward( &self, equation: &Equation ) -> Option<Vec<&'a dyn Operator>> {
+    let mut chosen = Vec::with_capacity( equation.values.len() - 1 );
+    let found = self.undo_last( &equation.values, equation.result, &mut chosen );
+    found.then( || {
+      chosen.reverse();
+      chosen
+    } )
+  }
+
+  fn undo_last( &self, values: &[isize], result: isize, chosen: &mut Vec<&'a dyn Operator> ) -> bool {
+    let (last, values) = values.split_last()
+      .expect( "an equation should have values" );
+    if values.is_empty() {
+      return *last == result;
+    }
+    for operator in self.operators {
+      // no partial value is zero or less
+      let Some(left) = operator.undo( result, *last ).filter( |left| *left > 0 ) else { continue };
+      chosen.push( *operator );
+      if self.undo_last( values, left, chosen ) {
+        return true;
+      }
+      chosen.pop();
+    }
+    false
+  }
+
+  fn forward( &self, equation: &Equation ) -> Option<Vec<&'a dyn Operator>> {
+    let mut chosen = Vec::with_capacity( equation.values.len() - 1 );
+    self.extend( &equation.values[1..], equation.result, Partial::new( equation.values[0] ), &mut chosen )
+      .then( || chosen.into_iter().map( |operator| self.operators[ operator ] ).collect() )
+  }
+
+  fn extend( &self, values: &[isize], result: isize, partial: Partial, chosen: &mut Vec<usize> ) -> bool {
+    let Some((value, values)) = values.split_first() else {
+      return partial.finish( self.operators ) == Some(result);
+    };
+    for operator in 0..self.operators.len() {
+      let mut next = partial.clone();
+      if next.push( self.operators, operator, *value, self.mode ).is_none() {
+        continue;
+      }
+      chosen.push( operator );
+      if self.extend( values, result, next, chosen ) {
+        return true;
+      }
+      chosen.pop();
+    }
+    false
+  }
+
+  /// Number of distinct operator assignments making `equation` true, `None` when more than a `u64` holds.
+  pub fn count( &self, equation: &Equation ) -> Option<u64> {
+    let mut counter = Counter::new( *self, equation );
+    let first = Partial::new( equation.values[0] );
+    counter.count( 0, &first )
+  }
+
+  /// Every operator assignment making `equation` true, lazily, see [`Solutions`].
+  pub fn solutions( &self, equation: &Equation ) -> Solutions<'a> {
+    let mut counter = Counter::new( *self, equation );
+    let first = Partial::new( equation.values[0] );
+    let stack = match counter.count( 0, &first ) {
+      Some(0) => Vec::new(),
+      _ => vec![(first, 0)],
+    };
+    Solutions { counter, stack, chosen: Vec::new() }
+  }
+}
+
+/// Number of ways to complete partial evaluations into an equation's result, memoized over (index of the last value
+/// evaluated, partial evaluation) : left to right, that partial evaluation is just the value so far. Counts are `None`
+/// past `u64::MAX`, which is still more than none.
+struct Counter<'a> {
+  solver: Solver<'a>,
+  values: Vec<isize>,
+  result: isize,
+  /// whether partial values only grow, so that one past the result is a dead end : positive values are needed
+  growing: bool,
+  memo: HashMap<(usize, Partial), Option<u64>>,
+}
+
+impl<'a> Counter<'a> {
+  fn new( solver: Solver<'a>, equation: &Equation ) -> Self {
+    let growing = solver.mode == Mode::LeftToRight && solver.operators.iter().all( |operator| operator.never_decreases() )
+      && equation.values.iter().all( |value| *value > 0 );
+    Self { solver, values: equation.values.clone(), result: equation.result, growing, memo: HashMap::new() }
+  }
+
+  fn count( &mut self, index: usize, partial: &Partial ) -> Option<u64> {
+    if index == self.values.len() - 1 {
+      return Some( u64::from( partial.clone().finish( self.solver.operators ) == Some(self.result) ) );
+    }
+    if self.growing && partial.last > self.result {
+      return Some( 0 );
+    }
+    if let Some(count) = self.memo.get( &(index, partial.clone()) ) {
+      return *count;
+    }
+    let mut count = Some( 0_u64 );
+    for operator in 0..self.solver.operators.len() {
+      if let Some(next) = self.next( partial, operator, index ) {
+        let more = self.count( index + 1, &next );
+        count = count.zip( more ).and_then( |(count, more)| count.checked_add( more ) );
+      }
+    }
+    self.memo.insert( (index, partial.clone()), count );
+    count
+  }
+
+  /// The partial evaluation after applying `operator` to the value following `index`.
+  fn next( &self, partial: &Partial, operator: usize, index: usize ) -> Option<Partial> {
+    let mut next = partial.clone();
+    next.push( self.solver.operators, operator, self.values[ index + 1 ], self.solver.mode )?;
+    Some( next )
+  }
+}
+
+/// Iterator over the operator assignments making an equation true, in the order of the solver's operators.
+///
+/// Depth first, only going down branches with solutions according to the counts, so that each solution comes after
+/// the counting of the branches before it, and never after a dead end.
+pub struct Solutions<'a> {
+  counter: Counter<'a>,
+  /// partial evaluation of each value of the current branch, with the next operator to try after it
+  stack: Vec<(Partial, usize)>,
+  chosen: Vec<usize>,
+}
+
+impl<'a> Iterator for Solutions<'a> {
+  type Item = Witness<'a>;
+
+  fn next( &mut self ) -> Option<Self::Item> {
+    let (solver, last) = (self.counter.solver, self.counter.values.len() - 1);
+    while !self.stack.is_empty() {
+      let index = self.stack.len() - 1;
+      let (partial, next_operator) = &mut self.stack[ index ];
+      if index == last {
+        let witness = Witness {
+          values: self.counter.values.clone(),
+          operators: self.chosen.iter().map( |operator| solver.operators[ *operator ] ).collect(),
+          mode: solver.mode,
+        };
+        self.stack.pop();
+        self.chosen.pop();
+        return Some( witness );
+      }
+      if *next_operator == solver.operators.len() {
+        self.stack.pop();
+        self.chosen.pop();
+        continue;
+      }
+      let operator = *next_operator;
+      *next_operator += 1;
+      let Some(next) = self.counter.next( partial, operator, index ) else { continue };
+      if self.counter.count( index + 1, &next ) != Some(0) {
+        self.chosen.push( operator );
+        self.stack.push( (next, 0) );
+      }
+    }
+    None
+  }
+}
+
+/// The power of `base` shifting a number left of `value`'s digits in that base, `None` when it overflows.
+fn digits_shift( value: isize, base: isize ) -> Option<isize> {
+  let digits = match value {
+    0 => 1,
+    _ => value.checked_ilog( base )? + 1,
+  };
+  base.checked_pow( digits )
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+// the puzzle's operators
+
+#[derive(Debug, Clone, Copy)]
+pub struct Add;
+
+impl fmt::Display for Add {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "+" ) }
+}
+
+impl Operator for Add {
+  fn apply( &self, left: isize, right: isize ) -> Option<isize> { left.checked_add( right ) }
+  fn precedence( &self ) -> u8 { 1 }
+  fn keeps_positive( &self ) -> bool { true }
+  fn never_decreases( &self ) -> bool { true }
+  fn invertible( &self ) -> bool { true }
+  fn undo( &self, result: isize, right: isize ) -> Option<isize> { result.checked_sub( right ) }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Mul;
+
+impl fmt::Display for Mul {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "*" ) }
+}
+
+impl Operator for Mul {
+  fn apply( &self, left: isize, right: isize ) -> Option<isize> { left.checked_mul( right ) }
+  fn precedence( &self ) -> u8 { 2 }
+  fn keeps_positive( &self ) -> bool { true }
+  fn never_decreases( &self ) -> bool { true }
+  fn invertible( &self ) -> bool { true }
+  fn undo( &self, result: isize, right: isize ) -> Option<isize> {
+    (right != 0 && result % right == 0).then( || result / right )
+  }
+}
+
+/// Decimal concatenation, binding tighter than any other operator as it builds a single number.
+#[derive(Debug, Clone, Copy)]
+pub struct Concat;
+
+impl fmt::Display for Concat {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "||" ) }
+}
+
+impl Operator for Concat {
+  fn apply( &self, left: isize, right: isize ) -> Option<isize> { BaseConcat( 10 ).apply( left, right ) }
+  fn precedence( &self ) -> u8 { 4 }
+  fn keeps_positive( &self ) -> bool { true }
+  fn never_decreases( &self ) -> bool { true }
+  fn invertible( &self ) -> bool { true }
+  fn undo( &self, result: isize, right: isize ) -> Option<isize> { BaseConcat( 10 ).undo( result, right ) }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+// more operators, for other equations
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sub;
+
+impl fmt::Display for Sub {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "-" ) }
+}
+
+impl Operator for Sub {
+  fn apply( &self, left: isize, right: isize ) -> Option<isize> { left.checked_sub( right ) }
+  fn precedence( &self ) -> u8 { 1 }
+  fn invertible( &self ) -> bool { true }
+  fn undo( &self, result: isize, right: isize ) -> Option<isize> { result.checked_add( right ) }
+}
+
+/// Integer division, rounding toward zero : several left operands give the same result, so it isn't invertible.
+#[derive(Debug, Clone, Copy)]
+pub struct Div;
+
+impl fmt::Display for Div {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "/" ) }
+}
+
+impl Operator for Div {
+  fn apply( &self, left: isize, right: isize ) -> Option<isize> { left.checked_div( right ) }
+  fn precedence( &self ) -> u8 { 2 }
+}
+
+/// Exponentiation : `x ^ 0` is 1 whatever `x`, and an even power has a negative root as well as a positive one, so it
+/// isn't invertible.
+#[derive(Debug, Clone, Copy)]
+pub struct Pow;
+
+impl fmt::Display for Pow {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "^" ) }
+}
+
+impl Operator for Pow {
+  fn apply( &self, left: isize, right: isize ) -> Option<isize> { left.checked_pow( u32::try_from( right ).ok()? ) }
+  fn precedence( &self ) -> u8 { 3 }
+  fn right_associative( &self ) -> bool { true }
+  fn keeps_positive( &self ) -> bool { true }
+  fn never_decreases( &self ) -> bool { true }
+}
+
+/// Concatenation of the digits in another base, e.g. `5 ||2 3` is `101` and `11`, so `10111` or 23.
+#[derive(Debug, Clone, Copy)]
+pub struct BaseConcat( pub isize );
+
+impl fmt::Display for BaseConcat {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "||{}", self.0 ) }
+}
+
+impl Operator for BaseConcat {
+  fn apply( &self, left: isize, right: isize ) -> Option<isize> {
+    if left < 0 || right < 0 {
+      return None;
+    }
+    left.checked_mul( digits_shift( right, self.0 )? )?.checked_add( right )
+  }
+  fn precedence( &self ) -> u8 { 4 }
+  fn keeps_positive( &self ) -> bool { true }
+  fn never_decreases( &self ) -> bool { true }
+  fn invertible( &self ) -> bool { true }
+  fn undo( &self, result: isize, right: isize ) -> Option<isize> {
+    if result < 0 || right < 0 {
+      return None;
+    }
+    let shift = digits_shift( right, self.0 )?;
+    (result % shift == right).then( || result / shift )
+  }
+}
+
+/// Each equation's result, with the expression making it true when it can be.
+pub struct Witnesses( pub Vec<(isize, Option<Witness<'static>>)> );
+
+impl fmt::Display for Witnesses {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    for (result, witness) in &self.0 {
+      match witness {
+        Some(witness) => writeln!( f, "{result} = {witness}" )?,
+        None => writeln!( f, "{result}: no solution" )?,
+      }
+    }
+    Ok(())
+  }
+}
+
+impl Report for Witnesses {
+  fn json( &self ) -> String {
+    json_array( self.0.iter()
+      .map( |(result, witness)| {
+        let expression = witness.as_ref().map_or( "null".to_owned(), |witness| json_string( &witness.to_string() ) );
+        format!( "{{\"result\": {result}, \"expression\": {expression}}}" )
+      } ) )
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+mod part_one {
+  use super::*;
+
+  pub(super) const SOLVER: Solver<'static> = Solver::new( &[&Add, &Mul], Mode::LeftToRight );
+
+  pub(super) fn compute_answer( equations: &[Equation] ) -> isize {
+    total_calibration( equations, SOLVER )
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_solve() {
+      let equation = Equation{ result: 190, values: vec![10,19] };
+      assert_eq!( "10 * 19", SOLVER.solve( &equation ).unwrap().to_string() );
+      let equation = Equation{ result: 3267, values: vec![81,40,27] };
+      assert_eq!( "81 * 40 + 27", SOLVER.solve( &equation ).unwrap().to_string() );
+      let equation = Equation{ result: 156, values: vec![15,6] };
+      assert!( SOLVER.solve( &equation ).is_none() );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 3749;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+mod part_two {
+  use super::*;
+
+  pub(super) const SOLVER: Solver<'static> = Solver::new( &[&Add, &Mul, &Concat], Mode::LeftToRight );
+
+  pub(super) fn compute_answer( equations: &[Equation] ) -> isize {
+    total_calibration( equations, SOLVER )
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_solve() {
+      let equation = Equation{ result: 7290, values: vec![6,8,6,15] };
+      let witness = SOLVER.solve( &equation ).unwrap();
+      assert_eq!( "(6 * 8) || 6 * 15", witness.to_string() );
+      assert_eq!( Some(7290), witness.evaluate() );
+      let equation = Equation{ result: 156, values: vec![15,6] };
+      assert_eq!( "15 || 6", SOLVER.solve( &equation ).unwrap().to_string() );
+    }
+
+    #[test]
+    fn test_overflow() {
+      // concatenating those overflows, so does multiplying them
+      let values = vec![isize::MAX / 10, 99];
+      assert_eq!( None, evaluate( &values, &[&Concat], Mode::LeftToRight ) );
+      assert!( SOLVER.solve( &Equation{ result: isize::MAX, values } ).is_none() );
+      assert!( SOLVER.solve( &Equation{ result: 7, values: vec![isize::MAX, isize::MAX, 7] } ).is_none() );
+      assert_eq!( None, digits_shift( isize::MAX, 10 ) );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 11387;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+      let equations = parse_input( &[TEST_INPUT; 50].join("\n") ).unwrap();
+      let serial = equations.iter()
+        .filter( |equation| SOLVER.solve( equation ).is_some() )
+        .map( |equation| equation.result )
+        .sum::<isize>();
+      assert_eq!( serial, compute_answer( &equations ) );
+    }
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+  use itertools::Itertools;
+  use super::*;
+
+  pub(super) const TEST_INPUT: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+  #[test]
+  fn test_parse_input() {
+    let equations = parse_input( TEST_INPUT ).unwrap();
+    for equation in equations {
+      println!( "{equation:?}" );
+    }
+
+    let error = parse_input( "190: 10 19\n3267: 81 4o 27" ).unwrap_err();
+    assert_eq!( (2, 10, "4o"), (error.line, error.column, error.text.as_str()) );
+    let error = parse_input( "190 10 19" ).unwrap_err();
+    assert_eq!( (1, 1), (error.line, error.column) );
+    // zero and negative numbers are numbers too
+    assert_eq!( vec![0, -2], parse_input( "-20: 0 -2" ).unwrap()[0].values );
+  }
+
+  #[test]
+  fn test_zero_and_negative_values() {
+    let equations = parse_input( "0: 5 0\n5: 0 5\n7: 7 0 0\n-3: 2 -5\n50: 5 0" ).unwrap();
+    for solver in [part_one::SOLVER, part_two::SOLVER] {
+      let solved = equations.iter().map( |equation| solver.solve( equation ).is_some() ).collect::<Vec<_>>();
+      assert_eq!( vec![true, true, true, true, solver.operators.len() == 3], solved );
+      for equation in &equations {
+        let count = brute_force_count( solver, equation );
+        assert_eq!( Some(count as u64), solver.count( equation ) );
+        assert_eq!( count, solver.solutions( equation ).count() );
+      }
+    }
+    assert_eq!( 5 + 7 - 3, total_calibration( &equations, part_one::SOLVER ) );
+  }
+
+  #[test]
+  fn test_evaluate() {
+    assert_eq!( Some(2), evaluate( &[10, 4, 3], &[&Sub, &Div], Mode::LeftToRight ) );
+    assert_eq!( Some(9), evaluate( &[10, 4, 3], &[&Sub, &Div], Mode::Precedence ) );
+    assert_eq!( Some(64), evaluate( &[2, 3, 2], &[&Pow, &Pow], Mode::LeftToRight ) );
+    assert_eq!( Some(512), evaluate( &[2, 3, 2], &[&Pow, &Pow], Mode::Precedence ) );
+    assert_eq!( Some(2 + 3 * 45), evaluate( &[2, 3, 4, 5], &[&Add, &Mul, &Concat], Mode::Precedence ) );
+    assert_eq!( Some(23), evaluate( &[5, 3], &[&BaseConcat(2)], Mode::LeftToRight ) );
+    assert_eq!( None, evaluate( &[5, 0], &[&Div], Mode::LeftToRight ) );
+  }
+
+  #[test]
+  fn test_custom_operators() {
+    let solver = Solver::new( &[&Sub, &Div], Mode::LeftToRight );
+    let witness = solver.solve( &Equation{ result: 2, values: vec![10, 4, 3] } ).unwrap();
+    assert_eq!( ("(10 - 4) / 3", Some(2)), (witness.to_string().as_str(), witness.evaluate()) );
+
+    let solver = Solver::new( &[&Add, &Pow], Mode::Precedence );
+    let witness = solver.solve( &Equation{ result: 513, values: vec![1, 2, 3, 2] } ).unwrap();
+    assert_eq!( "1 + 2 ^ 3 ^ 2", witness.to_string() );
+
+    let solver = Solver::new( &[&Mul, &BaseConcat(2)], Mode::LeftToRight );
+    assert_eq!( "5 ||2 3 * 2", solver.solve( &Equation{ result: 46, values: vec![5, 3, 2] } ).unwrap().to_string() );
+    assert_eq!( Some(5), BaseConcat( 2 ).undo( 23, 3 ) );
+
+    // both -3 ^ 2 and 3 ^ 2 are 9, so powers are searched forward
+    let solver = Solver::new( &[&Sub, &Pow], Mode::LeftToRight );
+    let equation = Equation{ result: 9, values: vec![1, 4, 2] };
+    assert!( !Solver::new( &[&Mul, &Pow], Mode::LeftToRight ).goes_backward( &equation ) );
+    assert_eq!( "(1 - 4) ^ 2", solver.solve( &equation ).unwrap().to_string() );
+  }
+
+  #[test]
+  fn test_backward_matches_forward() {
+    let mut equations = parse_input( TEST_INPUT ).unwrap();
+    equations.extend( parse_input( "0: 3 3 2\n4: 3 5 2\n-8: 1 3 3\n1: 5 3 1\n27: 3 3\n12: 4 2 1" ).unwrap() );
+    let solvers = [
+      part_one::SOLVER,
+      part_two::SOLVER,
+      Solver::new( &[&Add, &Mul, &BaseConcat(3)], Mode::LeftToRight ),
+      Solver::new( &[&Sub, &Pow], Mode::LeftToRight ),
+      Solver::new( &[&Add, &Sub, &Mul], Mode::LeftToRight ),
+    ];
+    for solver in solvers {
+      for equation in &equations {
+        let solved = solver.solve( equation );
+        assert_eq!( solver.count( equation ) != Some(0), solved.is_some(), "{equation:?}" );
+        assert!( solved.is_none_or( |witness| witness.evaluate() == Some(equation.result) ) );
+        if solver.goes_backward( equation ) {
+          assert_eq!( solver.backward( equation ).is_some(), solver.forward( equation ).is_some(), "{equation:?}" );
+        }
+      }
+    }
+    // partial values below zero can't be undone
+    let solver = Solver::new( &[&Sub, &Pow], Mode::LeftToRight );
+    assert!( !solver.goes_backward( &Equation { result: 0, values: vec![3, 3, 2] } ) );
+    assert!( part_two::SOLVER.goes_backward( &Equation { result: 0, values: vec![3, 3, 2] } ) );
+  }
+
+  /// Number of operator assignments making `equation` true, trying them all.
+  fn brute_force_count( solver: Solver<'_>, equation: &Equation ) -> usize {
+    itertools::repeat_n( solver.operators.iter().copied(), equation.values.len() - 1 )
+      .multi_cartesian_product()
+      .filter( |operators| evaluate( &equation.values, operators, solver.mode ) == Some(equation.result) )
+      .count()
+  }
+
+  #[test]
+  fn test_count() {
+    let solvers = [
+      part_one::SOLVER,
+      part_two::SOLVER,
+      Solver::new( &[&Add, &Mul, &Concat], Mode::Precedence ),
+      Solver::new( &[&Add, &Sub, &Mul, &Div], Mode::LeftToRight ),
+      Solver::new( &[&Add, &Sub, &Mul, &Div], Mode::Precedence ),
+    ];
+    let mut equations = parse_input( TEST_INPUT ).unwrap();
+    // repeated values, so that many assignments share partial evaluations
+    equations.push( Equation { result: 4, values: vec![2; 8] } );
+    equations.push( Equation { result: 1, values: vec![1; 9] } );
+    for solver in solvers {
+      for equation in &equations {
+        let expected = brute_force_count( solver, equation );
+        assert_eq!( Some(expected as u64), solver.count( equation ), "{equation:?}" );
+        let solutions = solver.solutions( equation ).collect::<Vec<_>>();
+        assert_eq!( expected, solutions.len(), "{equation:?}" );
+        assert!( solutions.iter().all( |witness| witness.evaluate() == Some(equation.result) ) );
+      }
+    }
+  }
+
+  #[test]
+  fn test_solutions() {
+    let equation = Equation { result: 3267, values: vec![81, 40, 27] };
+    let solutions = part_one::SOLVER.solutions( &equation ).map( |witness| witness.to_string() ).collect::<Vec<_>>();
+    assert_eq!( vec!["(81 + 40) * 27", "81 * 40 + 27"], solutions );
+
+    // any assignment works, far too many to enumerate, but they are counted and produced on demand
+    let solver = Solver::new( &[&Mul, &Pow], Mode::LeftToRight );
+    let equation = Equation { result: 1, values: vec![1; 40] };
+    assert_eq!( Some(1 << 39), solver.count( &equation ) );
+    let mut solutions = solver.solutions( &equation );
+    assert_eq!( "1 * 1 * 1", &solutions.next().unwrap().to_string()[..9] );
+    assert!( solutions.next().is_some() );
+
+    // up to the largest count a u64 holds, and beyond
+    assert_eq!( Some(1 << 63), solver.count( &Equation { result: 1, values: vec![1; 64] } ) );
+    let equation = Equation { result: 1, values: vec![1; 65] };
+    assert_eq!( None, solver.count( &equation ) );
+    assert_eq!( 3, solver.solutions( &equation ).take( 3 ).count() );
+    assert_eq!( 0, part_one::SOLVER.solutions( &Equation { result: 83, values: vec![17, 5] } ).count() );
+  }
+
+  #[test]
+  fn test_report() {
+    let equations = parse_input( TEST_INPUT ).unwrap();
+    let report = Day07::report( &equations, Part::Two ).unwrap();
+    assert!( report.to_string().starts_with( "190 = 10 * 19\n3267 = 81 * 40 + 27\n83: no solution\n156 = 15 || 6\n" ) );
+    assert!( report.json().starts_with( r#"[{"result": 190, "expression": "10 * 19"}, {"result": 3267, "expression": "81 * 40 + 27"}, {"result": 83, "expression": null}"# ) );
+  }
+}
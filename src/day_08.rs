@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use itertools::Itertools;
+use crate::{grid::Pos, render::{Canvas, Color, Overlay}, Mat2D, ParseError, Solution};
+
+type Network = HashMap<char, Vec<Pos>>;
+type City = (Mat2D<char>, Network);
+
+pub struct Day08;
+
+impl Solution for Day08 {
+  const DAY: u8 = 8;
+  type Input = City;
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+}
+
+fn parse_input( input: &str ) -> Result<City, ParseError> {
+  let mat = Mat2D::try_from_lines( input, Some, "a map cell" )?;
+  let network = mat.iter()
+    .filter_map( |(position, char)| match char {
+      'a'..='z' | 'A'..='Z' | '0'..='9' => Some((*char, Pos::from(position))),
+      _ => None,})
+    .into_group_map();
+  Ok(( mat, network ))
+}
+
+/// Antennas in cyan, resonant harmonics antinodes in blue, first antinodes as red '#'.
+fn visualize( city: &City ) -> Canvas {
+  let (mat, _) = city;
+  let first = part_one::antinodes( city ).into_iter()
+    .filter( |position| mat[*position] == '.' );
+  Canvas::with_palette( mat, |cell| (*cell != '.').then_some( Color::Cyan ) )
+    .overlay( &Overlay::visited( part_two::antinodes(city).into_iter().filter( |position| mat[*position] == '.' ) ) )
+    .overlay( &Overlay::new( first, Color::Red ).with_glyph( '#' ) )
+}
+
+mod part_one {
+  use std::collections::HashSet;
+  use super::*;
+
+  pub(super) fn compute_answer( city: &City ) -> usize {
+    antinodes( city ).len()
+  }
+
+  pub(super) fn antinodes( (mat, network): &City ) -> HashSet<Pos> {
+    let antinodes = |one: Pos, other: Pos| {
+      get_antinodes( mat, one, other ).into_iter().flatten()
+    };
+
+    network.values()
+      .flat_map( |positions| positions.iter().copied()
+        .tuple_combinations()
+        .flat_map( |(one, other)| antinodes( one, other )))
+      .collect()
+  }
+
+  fn get_antinodes( mat: &Mat2D<char>, one: Pos, other: Pos ) -> [Option<Pos>; 2] {
+    let inner = |first: Pos, second: Pos| {
+      second.step( second - first, mat )
+    };
+
+    let first = inner( one, other );
+    let second = inner( other, one );
+    [first, second]
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_get_antinodes() {
+      let one = Pos::new( 3, 4 );
+      let other = Pos::new( 5, 5 );
+      let expected = [Some(Pos::new(7, 6)), Some(Pos::new(1, 3))];
+      let actual = get_antinodes( &Mat2D::new(10, 10, '.'), one, other );
+      assert_eq!( expected, actual );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 14;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+mod part_two {
+  use std::{collections::HashSet, iter};
+  use super::*;
+
+  pub(super) fn compute_answer( city: &City ) -> usize {
+    antinodes( city ).len()
+  }
+
+  pub(super) fn antinodes( (mat, network): &City ) -> HashSet<Pos> {
+    let antinodes = |one: Pos, other: Pos| {
+      get_antinodes( mat, one, other )
+    };
+
+    network.values()
+      .flat_map( |positions| positions.iter().copied()
+        .tuple_combinations()
+        .flat_map( |(one, other)| antinodes( one, other )))
+      .collect()
+  }
+
+  fn get_antinodes( mat: &Mat2D<char>, one: Pos, other: Pos ) -> impl Iterator<Item=Pos> + use<'_> {
+    let inner = |first: Pos, second: Pos| {
+      let offset = second - first;
+      iter::successors(
+        Some( first ),
+        move |position| position.step( offset, mat ))
+    };
+
+    let first = inner( one, other );
+    let second = inner( other, one );
+    first.chain( second )
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT;
+
+    #[test]
+    fn test_get_antinodes() {
+      let one = Pos::new( 0, 0 );
+      let other = Pos::new( 2, 1 );
+      let expected = HashSet::from_iter( [(0, 0), (2, 1), (4, 2), (6, 3), (8, 4)].map( Pos::from ) );
+      let actual = get_antinodes( &Mat2D::new(10, 10, '.'), one, other ).collect::<HashSet<_>>();
+      assert_eq!( expected, actual );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 34;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  pub(super) const TEST_INPUT: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+  #[test]
+  fn test_parse_input() {
+    let network = parse_input( TEST_INPUT ).unwrap();
+    println!( "{network:?}" );
+  }
+}
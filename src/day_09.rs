@@ -0,0 +1,978 @@
+use std::{collections::{BTreeMap, BTreeSet}, fmt};
+use crate::{render::{Canvas, Color, Overlay}, report::{json_array, json_string, BoxedReport, Report}, trace::{Recorder, Trace}, ParseError, Part, Solution};
+
+#[derive(Debug)]
+enum Marker {
+    File,
+    Empty,
+}
+
+const MARKERS: &[Marker] = &[Marker::File, Marker::Empty];
+
+pub struct Day09;
+
+impl Solution for Day09 {
+  const DAY: u8 = 9;
+  type Input = Disk;
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+  fn trace( input: &Self::Input, part: Part, trace: &mut Trace<String> ) -> bool {
+    let strategy: &dyn CompactionStrategy = match part {
+      Part::One => &part_one::STRATEGY,
+      Part::Two => &part_two::STRATEGY,
+    };
+    replay( input, &compacted( input, strategy ), trace );
+    true
+  }
+  /// Both parts compare every strategy, the disk as given first.
+  fn report( input: &Self::Input, _part: Part ) -> Option<BoxedReport> {
+    let mut reports = compare( input, STRATEGIES );
+    reports.0.insert( 0, ("input".to_owned(), input.report()) );
+    Some( Box::new( reports ) )
+  }
+}
+
+fn parse_input( input: &str ) -> Result<Disk, ParseError> {
+  parse_dense( input, 0.. )
+}
+
+/// Disk from its dense format, the file digits belonging to the files `ids` give in turn.
+fn parse_dense( input: &str, mut ids: impl Iterator<Item=usize> ) -> Result<Disk, ParseError> {
+    let mut disk = Disk::default();
+    for ((idx, len), marker) in std::iter::zip( input.trim_end().char_indices(), MARKERS.iter().cycle() ) {
+      let digit = &input[idx..idx+len.len_utf8()];
+      let Some(len) = len.to_digit( 10 ) else {
+        return Err( ParseError::new( input, digit, "a length digit" ) );
+      };
+      let len = len as usize;
+      match marker {
+        Marker::File => {
+          let Some(file_id) = ids.next() else {
+            return Err( ParseError::new( input, digit, "a file with an id" ) );
+          };
+          if disk.files.len() <= file_id {
+            disk.files.resize( file_id + 1, Vec::new() );
+          }
+          let spans = &mut disk.files[ file_id ];
+          match spans.last_mut() {
+            _ if len == 0 => (),
+            Some(last) if last.end() == disk.len => last.len += len,
+            _ => spans.push( Span { start: disk.len, len } ),
+          }
+        },
+        Marker::Empty => (),
+      }
+      disk.len += len;
+    }
+    Ok( disk )
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Consecutive blocks, `len` of them from block `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Span {
+  pub start: usize,
+  pub len: usize,
+}
+
+impl Span {
+  pub fn end( &self ) -> usize {
+    self.start + self.len
+  }
+}
+
+/// Blocks of a file moved by a compaction, from the `from` span to the span of the same length starting at `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+  pub file_id: usize,
+  pub from: Span,
+  pub to: usize,
+}
+
+impl fmt::Display for Move {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    write!( f, "move {} block(s) of file {} from {} to {}", self.from.len, self.file_id, self.from.start, self.to )
+  }
+}
+
+/// Disk as the spans of blocks of each file, free blocks being the ones no file covers.
+///
+/// Disks are equal when their blocks are, whatever moves led there.
+#[derive(Debug, Clone, Default)]
+pub struct Disk {
+  /// Spans of each file by id, in block order : a single one until the file gets fragmented.
+  files: Vec<Vec<Span>>,
+  len: usize,
+  /// Every move since parsing, in order.
+  moves: Vec<Move>,
+}
+
+impl PartialEq for Disk {
+  fn eq( &self, other: &Self ) -> bool {
+    self.files == other.files && self.len == other.len
+  }
+}
+
+impl Eq for Disk {}
+
+impl Disk {
+  /// Number of blocks, free or not.
+  pub fn len( &self ) -> usize {
+    self.len
+  }
+
+  pub fn is_empty( &self ) -> bool {
+    self.len == 0
+  }
+
+  /// Spans of each file, by file id.
+  pub fn files( &self ) -> &[Vec<Span>] {
+    &self.files
+  }
+
+  /// Moves made on the disk, in order.
+  pub fn moves( &self ) -> &[Move] {
+    &self.moves
+  }
+
+  /// Number of blocks used by files.
+  pub fn used( &self ) -> usize {
+    self.files.iter().flatten().map( |span| span.len ).sum()
+  }
+
+  /// Number of files split in several spans.
+  pub fn fragmented_files( &self ) -> usize {
+    self.files.iter().filter( |spans| spans.len() > 1 ).count()
+  }
+
+  /// Every file span with its file id, in block order.
+  pub fn file_spans( &self ) -> Vec<(usize, Span)> {
+    let mut spans = self.files.iter().enumerate()
+      .flat_map( |(file_id, spans)| spans.iter().map( move |span| (file_id, *span) ) )
+      .collect::<Vec<_>>();
+    spans.sort_unstable_by_key( |(_, span)| span.start );
+    spans
+  }
+
+  /// Free spans in block order, each as long as possible.
+  pub fn free_spans( &self ) -> Vec<Span> {
+    let mut free = Vec::new();
+    let mut start = 0;
+    for (_, span) in self.file_spans().into_iter().chain( [(0, Span { start: self.len, len: 0 })] ) {
+      if span.start > start {
+        free.push( Span { start, len: span.start - start } );
+      }
+      start = span.end();
+    }
+    free
+  }
+
+  /// Free spans with a file after them, the free space at the end of the disk not being a gap.
+  pub fn gaps( &self ) -> Vec<Span> {
+    let mut free = self.free_spans();
+    if free.last().is_some_and( |span| span.end() == self.len ) {
+      free.pop();
+    }
+    free
+  }
+
+  /// One entry per block, with the id of the file it belongs to.
+  pub fn blocks( &self ) -> Vec<Option<usize>> {
+    let mut blocks = vec![None; self.len];
+    for (file_id, span) in self.file_spans() {
+      blocks[ span.start..span.end() ].fill( Some(file_id) );
+    }
+    blocks
+  }
+
+  pub fn checksum( &self ) -> usize {
+    self.files.iter().enumerate()
+      .flat_map( |(file_id, spans)| spans.iter().map( move |span| file_id * (span.start * span.len + span.len * (span.len - 1) / 2) ) )
+      .sum()
+  }
+
+  /// Moves the `from` blocks of a file to the free blocks from `to`, logging the move.
+  ///
+  /// Panics if the file has no span holding `from`. The target blocks aren't checked to be free.
+  pub fn move_blocks( &mut self, file_id: usize, from: Span, to: usize ) {
+    let spans = &mut self.files[ file_id ];
+    let idx = spans.iter().position( |span| span.start <= from.start && from.end() <= span.end() )
+      .unwrap_or_else( || panic!( "file {file_id} should hold blocks {}..{}", from.start, from.end() ) );
+    let span = spans.remove( idx );
+    spans.extend( [
+      Span { start: span.start, len: from.start - span.start },
+      Span { start: from.end(), len: span.end() - from.end() },
+      Span { start: to, len: from.len },
+    ].into_iter().filter( |span| span.len > 0 ) );
+    merge_spans( spans );
+    self.moves.push( Move { file_id, from, to } );
+  }
+
+  pub fn report( &self ) -> DiskReport {
+    let offsets = self.files.iter().filter_map( |spans| spans.first() ).map( |span| span.start ).collect::<Vec<_>>();
+    DiskReport {
+      blocks: self.len,
+      used: self.used(),
+      free_gaps: self.gaps().len(),
+      largest_free_span: self.free_spans().iter().map( |span| span.len ).max().unwrap_or( 0 ),
+      fragmented_files: self.fragmented_files(),
+      average_file_offset: match offsets.len() {
+        0 => 0.0,
+        count => offsets.iter().sum::<usize>() as f64 / count as f64,
+      },
+      blocks_moved: self.moves.iter().map( |mv| mv.from.len ).sum(),
+      checksum: self.checksum(),
+    }
+  }
+
+  /// The disk in the puzzle's dense format.
+  ///
+  /// The format numbers files in the order they appear, so their actual ids are listed aside, one per file digit.
+  /// A file span longer than 9 blocks is split by empty free spans, a free span by empty files, and files without
+  /// blocks are left out.
+  pub fn to_dense( &self ) -> DenseMap {
+    let mut dense = DenseMap::default();
+    let mut end = 0;
+    for (file_id, span) in self.file_spans() {
+      dense.push( None, span.start - end );
+      dense.push( Some(file_id), span.len );
+      end = span.end();
+    }
+    dense.push( None, self.len - end );
+    dense
+  }
+
+  /// The disk `to_dense` gave.
+  pub fn from_dense( dense: &DenseMap ) -> Result<Disk, ParseError> {
+    parse_dense( &dense.digits, dense.ids.iter().copied() )
+  }
+}
+
+/// Fragmentation of a disk, and how many blocks were moved to get there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskReport {
+  pub blocks: usize,
+  pub used: usize,
+  /// Free spans with a file after them.
+  pub free_gaps: usize,
+  pub largest_free_span: usize,
+  /// Files split in several spans.
+  pub fragmented_files: usize,
+  /// Mean first block of the files having blocks, 0 when none has.
+  pub average_file_offset: f64,
+  /// Blocks moved since parsing.
+  pub blocks_moved: usize,
+  pub checksum: usize,
+}
+
+impl fmt::Display for DiskReport {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    writeln!( f, "{} of {} blocks used, checksum {}", self.used, self.blocks, self.checksum )?;
+    writeln!( f, "{} free gap(s), largest free span of {} block(s)", self.free_gaps, self.largest_free_span )?;
+    writeln!( f, "{} fragmented file(s), average file offset {:.1}", self.fragmented_files, self.average_file_offset )?;
+    writeln!( f, "{} block(s) moved", self.blocks_moved )
+  }
+}
+
+impl Report for DiskReport {
+  fn json( &self ) -> String {
+    format!( "{{\"blocks\": {}, \"used\": {}, \"free_gaps\": {}, \"largest_free_span\": {}, \"fragmented_files\": {}, \
+      \"average_file_offset\": {}, \"blocks_moved\": {}, \"checksum\": {}}}",
+      self.blocks, self.used, self.free_gaps, self.largest_free_span, self.fragmented_files, self.average_file_offset,
+      self.blocks_moved, self.checksum )
+  }
+}
+
+/// A disk in the puzzle's dense format : digits alternating the lengths of file spans and free spans, along with the
+/// file id of each file span.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DenseMap {
+  pub digits: String,
+  pub ids: Vec<usize>,
+}
+
+impl DenseMap {
+  /// Appends `len` blocks of a file, or free ones, as digits of at most 9, keeping file and free digits alternating.
+  fn push( &mut self, file_id: Option<usize>, mut len: usize ) {
+    while len > 0 {
+      let run = len.min( 9 );
+      let files_turn = self.digits.len().is_multiple_of( 2 );
+      if files_turn != file_id.is_some() {
+        self.digits.push( '0' );
+        if files_turn {
+          self.ids.push( self.ids.last().copied().unwrap_or( 0 ) );
+        }
+      }
+      self.digits.push( char::from_digit( run as u32, 10 ).unwrap() );
+      self.ids.extend( file_id );
+      len -= run;
+    }
+  }
+}
+
+impl fmt::Display for DenseMap {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    write!( f, "{}", self.digits )
+  }
+}
+
+/// Free spans, merged as blocks get freed, by start and by length, each length's starts in order. The leftmost start
+/// of each length is also kept in a [`MinTree`] indexed by length, so that the spans fitting a file are searched in
+/// logarithmic time, however many distinct lengths merging spans makes.
+struct FreeLists {
+  by_start: BTreeMap<usize, usize>,
+  by_len: BTreeMap<usize, BTreeSet<usize>>,
+  leftmost_by_len: MinTree,
+}
+
+impl FreeLists {
+  /// Free spans of `len` blocks in all, which no span can outgrow, moving files never changing that number.
+  fn new( len: usize, spans: impl IntoIterator<Item=Span> ) -> Self {
+    let mut free = Self { by_start: BTreeMap::new(), by_len: BTreeMap::new(), leftmost_by_len: MinTree::new( len + 1 ) };
+    for span in spans {
+      free.insert( span );
+    }
+    free
+  }
+
+  /// Frees `span`, merging it with the free spans it touches.
+  fn insert( &mut self, span: Span ) {
+    if span.len == 0 {
+      return;
+    }
+    let mut merged = span;
+    if let Some((&start, &len)) = self.by_start.range( ..span.start ).next_back() {
+      if start + len == span.start {
+        self.take( Span { start, len } );
+        merged = Span { start, len: len + merged.len };
+      }
+    }
+    if let Some(&len) = self.by_start.get( &span.end() ) {
+      self.take( Span { start: span.end(), len } );
+      merged.len += len;
+    }
+    self.by_start.insert( merged.start, merged.len );
+    let starts = self.by_len.entry( merged.len ).or_default();
+    starts.insert( merged.start );
+    self.leftmost_by_len.set( merged.len, starts.first().copied() );
+  }
+
+  /// The leftmost free span of at least `len` blocks starting before `before`.
+  fn leftmost( &self, len: usize, before: usize ) -> Option<Span> {
+    let start = self.leftmost_by_len.min_from( len ).filter( |start| *start < before )?;
+    Some( Span { start, len: self.by_start[ &start ] } )
+  }
+
+  /// The smallest free span of at least `len` blocks starting before `before`, the leftmost of them on ties.
+  fn smallest( &self, len: usize, before: usize ) -> Option<Span> {
+    let len = self.leftmost_by_len.first_below( len, before )?;
+    Some( Span { start: self.leftmost_by_len.get( len ), len } )
+  }
+
+  /// The largest free span starting before `before`, the leftmost of them on ties, if it has at least `len` blocks.
+  fn largest( &self, len: usize, before: usize ) -> Option<Span> {
+    let len = self.leftmost_by_len.last_below( len, before )?;
+    Some( Span { start: self.leftmost_by_len.get( len ), len } )
+  }
+
+  /// Removes the free span `span`.
+  fn take( &mut self, span: Span ) {
+    self.by_start.remove( &span.start );
+    let starts = self.by_len.get_mut( &span.len ).expect( "a free span should be listed by its length" );
+    starts.remove( &span.start );
+    self.leftmost_by_len.set( span.len, starts.first().copied() );
+    if starts.is_empty() {
+      self.by_len.remove( &span.len );
+    }
+  }
+}
+
+/// Segment tree over the values of indices `0..len`, all `usize::MAX` at first, giving the least value from an index
+/// on, and the first or last index from an index on holding a value below a bound, in logarithmic time.
+struct MinTree {
+  /// Number of leaves, a power of two.
+  leaves: usize,
+  /// Least value under each node, the root being node 1 and the children of node `n` nodes `2n` and `2n+1`, the
+  /// leaves following the inner nodes.
+  mins: Vec<usize>,
+}
+
+impl MinTree {
+  fn new( len: usize ) -> Self {
+    let leaves = len.next_power_of_two();
+    Self { leaves, mins: vec![usize::MAX; 2 * leaves] }
+  }
+
+  fn get( &self, index: usize ) -> usize {
+    self.mins[ self.leaves + index ]
+  }
+
+  /// Sets the value of `index`, back to `usize::MAX` when `None`.
+  fn set( &mut self, index: usize, value: Option<usize> ) {
+    let mut node = self.leaves + index;
+    self.mins[ node ] = value.unwrap_or( usize::MAX );
+    while node > 1 {
+      node /= 2;
+      self.mins[ node ] = self.mins[ 2*node ].min( self.mins[ 2*node + 1 ] );
+    }
+  }
+
+  /// The least value of the indices from `from` on, `None` if they are all `usize::MAX`.
+  fn min_from( &self, from: usize ) -> Option<usize> {
+    if from >= self.leaves {
+      return None;
+    }
+    // climbing from the leaf, every right sibling of the path covers indices after `from`
+    let mut node = self.leaves + from;
+    let mut min = self.mins[ node ];
+    while node > 1 {
+      if node.is_multiple_of( 2 ) {
+        min = min.min( self.mins[ node + 1 ] );
+      }
+      node /= 2;
+    }
+    (min != usize::MAX).then_some( min )
+  }
+
+  /// The first index from `from` on holding a value below `bound`.
+  fn first_below( &self, from: usize, bound: usize ) -> Option<usize> {
+    self.find_below( 1, 0..self.leaves, from, bound, false )
+  }
+
+  /// The last index from `from` on holding a value below `bound`.
+  fn last_below( &self, from: usize, bound: usize ) -> Option<usize> {
+    self.find_below( 1, 0..self.leaves, from, bound, true )
+  }
+
+  /// Descends from `node`, covering the indices `range`, toward the first or `last` index from `from` on with a value
+  /// below `bound`. Subtrees entirely from `from` on are only entered when they hold such a value, so that only the
+  /// path along `from` may be left without finding one.
+  fn find_below( &self, node: usize, range: std::ops::Range<usize>, from: usize, bound: usize, last: bool ) -> Option<usize> {
+    if range.end <= from || self.mins[ node ] >= bound {
+      return None;
+    }
+    if node >= self.leaves {
+      return Some( range.start );
+    }
+    let middle = range.start + (range.end - range.start) / 2;
+    let (left, right) = ((2*node, range.start..middle), (2*node + 1, middle..range.end));
+    let (first, second) = if last { (right, left) } else { (left, right) };
+    self.find_below( first.0, first.1, from, bound, last )
+      .or_else( || self.find_below( second.0, second.1, from, bound, last ) )
+  }
+}
+
+/// Sorts `spans` and joins the ones touching each other.
+fn merge_spans( spans: &mut Vec<Span> ) {
+  spans.sort_unstable();
+  let mut merged: Vec<Span> = Vec::with_capacity( spans.len() );
+  for span in spans.drain( .. ) {
+    match merged.last_mut() {
+      Some(last) if last.end() == span.start => last.len += span.len,
+      _ => merged.push( span ),
+    }
+  }
+  *spans = merged;
+}
+
+/// Disk in the puzzle notation, '.' for free blocks and file ids written in base 36, modulo 36.
+fn disk_map( disk: &Disk ) -> String {
+  disk.blocks().iter()
+    .map( |block| block.map_or( '.', |file_id| char::from_digit( (file_id % 36) as u32, 36 ).unwrap() ) )
+    .collect()
+}
+
+/// The disk map as given, then compacted by each part's strategy, under the name of each, wrapped every `VIEW_WIDTH`
+/// blocks. Files are in a color cycling with their id, so that neighbouring files stand apart.
+fn visualize( disk: &Disk ) -> Canvas {
+  const VIEW_WIDTH: usize = 100;
+  const COLORS: [Color; 6] = [Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan];
+
+  let sections = [
+    ("input".to_owned(), disk.clone()),
+    (part_one::STRATEGY.to_string(), compacted( disk, &part_one::STRATEGY )),
+    (part_two::STRATEGY.to_string(), compacted( disk, &part_two::STRATEGY )),
+  ];
+  let mut text = String::new();
+  let mut cells = vec![Vec::new(); COLORS.len()];
+  let mut row = 0;
+  for (name, disk) in sections {
+    text += &format!( "{name}\n" );
+    row += 1;
+    let map = disk_map( &disk ).chars().collect::<Vec<_>>();
+    for line in map.chunks( VIEW_WIDTH ) {
+      text.extend( line );
+      text.push( '\n' );
+    }
+    for (block, file_id) in disk.blocks().iter().enumerate() {
+      if let Some(file_id) = file_id {
+        cells[ file_id % COLORS.len() ].push( (row + block / VIEW_WIDTH, block % VIEW_WIDTH) );
+      }
+    }
+    row += map.len().div_ceil( VIEW_WIDTH );
+  }
+  std::iter::zip( cells, COLORS )
+    .fold( Canvas::text( &text ), |canvas, (cells, color)| canvas.overlay( &Overlay::new( cells, color ) ) )
+}
+
+/// Records the moves `compacted` went through, replayed on `disk`.
+fn replay( disk: &Disk, compacted: &Disk, recorder: &mut impl Recorder<String> ) {
+  let mut disk = disk.clone();
+  for mv in &compacted.moves[ disk.moves.len().. ] {
+    disk.move_blocks( mv.file_id, mv.from, mv.to );
+    recorder.record( || (mv.to_string(), disk_map( &disk )) );
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// A way to compact a disk, moving file blocks left with `Disk::move_blocks`, displayed as its name.
+pub trait CompactionStrategy: fmt::Display + fmt::Debug + Sync {
+  fn compact( &self, disk: &mut Disk );
+}
+
+/// Copy of `disk` compacted by `strategy`.
+pub fn compacted( disk: &Disk, strategy: &dyn CompactionStrategy ) -> Disk {
+  let mut disk = disk.clone();
+  strategy.compact( &mut disk );
+  disk
+}
+
+/// Every strategy this module provides.
+pub const STRATEGIES: &[&dyn CompactionStrategy] = &[&BlockByBlock, &FirstFit, &BestFit, &WorstFit, &LeftmostGap];
+
+/// Report of the disk each strategy left, by strategy name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyReports( pub Vec<(String, DiskReport)> );
+
+/// Compacts a copy of `disk` with each strategy.
+pub fn compare( disk: &Disk, strategies: &[&dyn CompactionStrategy] ) -> StrategyReports {
+  StrategyReports( strategies.iter()
+    .map( |strategy| (strategy.to_string(), compacted( disk, *strategy ).report()) )
+    .collect() )
+}
+
+impl fmt::Display for StrategyReports {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    writeln!( f, "{:<16}{:>8}{:>10}{:>12}{:>12}{:>10}{:>18}", "strategy", "gaps", "largest", "fragmented", "avg offset", "moved", "checksum" )?;
+    for (strategy, report) in &self.0 {
+      writeln!( f, "{strategy:<16}{:>8}{:>10}{:>12}{:>12.1}{:>10}{:>18}", report.free_gaps, report.largest_free_span,
+        report.fragmented_files, report.average_file_offset, report.blocks_moved, report.checksum )?;
+    }
+    Ok(())
+  }
+}
+
+impl Report for StrategyReports {
+  fn json( &self ) -> String {
+    json_array( self.0.iter()
+      .map( |(strategy, report)| format!( "{{\"strategy\": {}, \"disk\": {}}}", json_string( strategy ), report.json() ) ) )
+  }
+}
+
+/// Moves blocks one by one from the end of the disk to its leftmost free block, until there's no gap left, splitting
+/// files as needed.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockByBlock;
+
+impl fmt::Display for BlockByBlock {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "block-by-block" ) }
+}
+
+impl CompactionStrategy for BlockByBlock {
+  /// In the end, the blocks past the number of used blocks have filled the free blocks before it, in reverse order :
+  /// the free spans are filled at once, with the file spans taken from the end.
+  fn compact( &self, disk: &mut Disk ) {
+    let used = disk.used();
+    let holes = disk.free_spans().into_iter()
+      .filter( |span| span.start < used )
+      .collect::<Vec<_>>();
+
+    let mut sources = disk.file_spans().into_iter()
+      .filter( |(_, span)| span.end() > used )
+      .map( |(file_id, span)| {
+        let start = span.start.max( used );
+        (file_id, Span { start, len: span.end() - start })
+      } )
+      .collect::<Vec<_>>();
+
+    for hole in holes {
+      let mut hole = Span { start: hole.start, len: hole.len.min( used - hole.start ) };
+      while hole.len > 0 {
+        let (file_id, source) = sources.last_mut()
+          .expect( "there should be as many blocks to move as free blocks before the end of the used ones" );
+        let len = hole.len.min( source.len );
+        source.len -= len;
+        let (taken, file_id) = (Span { start: source.end(), len }, *file_id);
+        if source.len == 0 {
+          sources.pop();
+        }
+        disk.move_blocks( file_id, taken, hole.start );
+        hole = Span { start: hole.start + len, len: hole.len - len };
+      }
+    }
+  }
+}
+
+/// Moves each whole file once, by decreasing id, to the free span `choose` finds among the free spans, given the
+/// file's length and the start it must come before. Files already fragmented stay put. Returns whether any file moved.
+///
+/// The span a file leaves is freed, as files with lower ids may lie right of it once file ids aren't in block order,
+/// as after a compaction.
+fn compact_whole_files( disk: &mut Disk, choose: impl Fn( &FreeLists, usize, usize ) -> Option<Span> ) -> bool {
+  let mut free = FreeLists::new( disk.len() - disk.used(), disk.free_spans() );
+  let mut moved = false;
+  for file_id in (0..disk.files.len()).rev() {
+    let [source] = disk.files[ file_id ][..] else { continue };
+    let Some(target) = choose( &free, source.len, source.start ) else { continue };
+    free.take( target );
+    free.insert( Span { start: target.start + source.len, len: target.len - source.len } );
+    free.insert( source );
+    disk.move_blocks( file_id, source, target.start );
+    moved = true;
+  }
+  moved
+}
+
+/// Moves each whole file once, by decreasing id, to the leftmost free span before it large enough to hold it.
+#[derive(Debug, Clone, Copy)]
+pub struct FirstFit;
+
+impl fmt::Display for FirstFit {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "first-fit" ) }
+}
+
+impl CompactionStrategy for FirstFit {
+  fn compact( &self, disk: &mut Disk ) {
+    compact_whole_files( disk, FreeLists::leftmost );
+  }
+}
+
+/// Moves each whole file once, by decreasing id, to the smallest free span before it large enough to hold it,
+/// the leftmost of them on ties, leaving the large spans to the large files.
+#[derive(Debug, Clone, Copy)]
+pub struct BestFit;
+
+impl fmt::Display for BestFit {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "best-fit" ) }
+}
+
+impl CompactionStrategy for BestFit {
+  fn compact( &self, disk: &mut Disk ) {
+    compact_whole_files( disk, FreeLists::smallest );
+  }
+}
+
+/// Moves each whole file once, by decreasing id, to the largest free span before it, the leftmost of them on ties,
+/// leaving the most room for other files after it.
+#[derive(Debug, Clone, Copy)]
+pub struct WorstFit;
+
+impl fmt::Display for WorstFit {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "worst-fit" ) }
+}
+
+impl CompactionStrategy for WorstFit {
+  fn compact( &self, disk: &mut Disk ) {
+    compact_whole_files( disk, FreeLists::largest );
+  }
+}
+
+/// Moves whole files, by decreasing id, to the leftmost free span before them large enough to hold them, as first-fit
+/// does, but going over the files again until none can move.
+#[derive(Debug, Clone, Copy)]
+pub struct LeftmostGap;
+
+impl fmt::Display for LeftmostGap {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "leftmost-gap" ) }
+}
+
+impl CompactionStrategy for LeftmostGap {
+  /// Each move takes a file left, so this ends.
+  fn compact( &self, disk: &mut Disk ) {
+    while compact_whole_files( disk, FreeLists::leftmost ) {}
+  }
+}
+
+mod part_one {
+  use super::*;
+
+  pub(super) const STRATEGY: BlockByBlock = BlockByBlock;
+
+  pub(super) fn compute_answer( disk: &Disk ) -> usize {
+    compacted( disk, &STRATEGY ).checksum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::{TEST_INPUT_1, TEST_INPUT_2};
+
+    #[test]
+    fn test_compact() {
+      let disk = compacted( &parse_input( TEST_INPUT_1 ).unwrap(), &STRATEGY );
+      assert_eq!( "022111222......", disk_map(&disk) );
+
+      let disk = compacted( &parse_input( TEST_INPUT_2 ).unwrap(), &STRATEGY );
+      assert_eq!( "0099811188827773336446555566..............", disk_map(&disk) );
+      let expected = vec![Span { start: 18, len: 1 }, Span { start: 21, len: 1 }, Span { start: 26, len: 2 }];
+      assert_eq!( expected, disk.files()[6] );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 1928;
+      let actual = compute_answer( &parse_input(TEST_INPUT_2).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+mod part_two {
+  use super::*;
+
+  pub(super) const STRATEGY: FirstFit = FirstFit;
+
+  pub(super) fn compute_answer( disk: &Disk ) -> usize {
+    compacted( disk, &STRATEGY ).checksum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT_2;
+
+    #[test]
+    fn test_compact() {
+      let disk = compacted( &parse_input( TEST_INPUT_2 ).unwrap(), &STRATEGY );
+      assert_eq!( "00992111777.44.333....5555.6666.....8888..", disk_map(&disk) );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 2858;
+      let actual = compute_answer( &parse_input(TEST_INPUT_2).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  pub(super) const TEST_INPUT_1: &str = "12345";
+  pub(super) const TEST_INPUT_2: &str = "2333133121414131402";
+
+  #[test]
+  fn test_parse_input() {
+    let expected = &[
+      Some(0),
+      None, None,
+      Some(1), Some(1), Some(1),
+      None, None, None, None,
+      Some(2), Some(2), Some(2), Some(2), Some(2)
+    ];
+    let actual = parse_input( TEST_INPUT_1 ).unwrap();
+    assert_eq!( expected, &actual.blocks()[..] );
+    assert_eq!( vec![Span { start: 1, len: 2 }, Span { start: 6, len: 4 }], actual.free_spans() );
+
+    let actual = parse_input( TEST_INPUT_2 ).unwrap();
+    assert_eq!( "00...111...2...333.44.5555.6666.777.888899", disk_map(&actual) );
+
+    // an empty file takes no block
+    assert_eq!( "0..22", disk_map( &parse_input( "12002" ).unwrap() ) );
+
+    let error = parse_input( "12a45" ).unwrap_err();
+    assert_eq!( (1, 3, "a"), (error.line, error.column, error.text.as_str()) );
+  }
+
+  /// Checksum of the block view.
+  fn block_checksum( disk: &Disk ) -> usize {
+    disk.blocks().iter().enumerate()
+      .filter_map( |(pos, block)| block.map( |file_id| pos * file_id ) )
+      .sum()
+  }
+
+  #[test]
+  fn test_checksum() {
+    let disk = parse_input( TEST_INPUT_2 ).unwrap();
+    assert_eq!( block_checksum(&disk), disk.checksum() );
+    for strategy in STRATEGIES {
+      let disk = compacted( &disk, *strategy );
+      assert_eq!( block_checksum(&disk), disk.checksum(), "{strategy}" );
+    }
+  }
+
+  #[test]
+  fn test_visualize() {
+    let canvas = visualize( &parse_input( TEST_INPUT_1 ).unwrap() );
+    let lines = canvas.to_string().lines().map( |line| line.trim_end().to_owned() ).collect::<Vec<_>>();
+    assert_eq!( ["input", "0..111....22222", "block-by-block", "022111222......", "first-fit", "0..111....22222"], lines[..] );
+    let ansi = canvas.ansi().to_string();
+    assert_eq!( Some("\x1b[31m0\x1b[0m..\x1b[32m111\x1b[0m....\x1b[33m22222\x1b[0m"), ansi.lines().nth( 1 ) );
+  }
+
+  #[test]
+  fn test_free_lists() {
+    // blocks freed and taken pseudo-randomly, the searches checked against scanning the free blocks
+    const LEN: usize = 300;
+    let mut free = FreeLists::new( LEN, [] );
+    let mut blocks = [false; LEN];
+    let mut state = 11_u64;
+    let mut next = |modulo: usize| {
+      state = state.wrapping_mul( 6364136223846793005 ).wrapping_add( 1442695040888963407 );
+      (state >> 33) as usize % modulo
+    };
+    for _ in 0..2000 {
+      let start = next( LEN );
+      let len = (start..LEN.min( start + next( 12 ) )).take_while( |block| !blocks[*block] ).count();
+      free.insert( Span { start, len } );
+      blocks[ start..start+len ].fill( true );
+
+      let spans = (0..LEN)
+        .filter( |block| blocks[*block] && (*block == 0 || !blocks[block-1]) )
+        .map( |start| Span { start, len: blocks[start..].iter().take_while( |free| **free ).count() } )
+        .collect::<Vec<_>>();
+      let (len, before) = (1 + next( 20 ), next( LEN + 1 ));
+      let fitting = spans.iter().filter( |span| span.len >= len && span.start < before );
+      assert_eq!( fitting.clone().next().copied(), free.leftmost( len, before ) );
+      assert_eq!( fitting.clone().min_by_key( |span| span.len ).copied(), free.smallest( len, before ) );
+      let largest = spans.iter().filter( |span| span.start < before ).rev().max_by_key( |span| span.len );
+      assert_eq!( largest.filter( |span| span.len >= len ).copied(), free.largest( len, before ) );
+
+      if let Some(span) = free.leftmost( len, before ).filter( |_| next( 2 ) == 0 ) {
+        free.take( span );
+        blocks[ span.start..span.end() ].fill( false );
+      }
+    }
+  }
+
+  #[test]
+  fn test_strategies() {
+    let disk = parse_input( TEST_INPUT_2 ).unwrap();
+    let maps = STRATEGIES.iter().map( |strategy| disk_map( &compacted( &disk, *strategy ) ) ).collect::<Vec<_>>();
+    let expected = [
+      "0099811188827773336446555566..............",
+      "00992111777.44.333....5555.6666.....8888..",
+      "00992111777.44.333....5555.6666.....8888..",
+      "00992111777.44.333....5555.6666.....8888..",
+      "00992111777.44.33388885555.6666...........",
+    ];
+    assert_eq!( &expected[..], &maps[..] );
+
+    // the first free span fitting, the smallest, or the largest
+    let disk = parse_input( "1412102" ).unwrap();
+    assert_eq!( "03321......", disk_map( &compacted( &disk, &FirstFit ) ) );
+    assert_eq!( "021...33...", disk_map( &compacted( &disk, &BestFit ) ) );
+    let disk = parse_input( "1214102" ).unwrap();
+    assert_eq!( "03312......", disk_map( &compacted( &disk, &FirstFit ) ) );
+    assert_eq!( "021.33.....", disk_map( &compacted( &disk, &WorstFit ) ) );
+
+    // space left by a file is free for the files with lower ids after it
+    let disk = Disk::from_dense( &DenseMap { digits: "12102".to_owned(), ids: vec![2, 1, 0] } ).unwrap();
+    assert_eq!( "2..100", disk_map(&disk) );
+    assert_eq!( "2100..", disk_map( &compacted( &disk, &FirstFit ) ) );
+    assert_eq!( "2100..", disk_map( &compacted( &disk, &LeftmostGap ) ) );
+
+    // a compacted disk read back is compacted further, first-fit now using the space it left
+    let disk = Disk::from_dense( &compacted( &parse_input( TEST_INPUT_2 ).unwrap(), &FirstFit ).to_dense() ).unwrap();
+    assert_eq!( "00992111777.44.33388885555.6666...........", disk_map( &compacted( &disk, &FirstFit ) ) );
+    assert_eq!( compacted( &disk, &LeftmostGap ), compacted( &disk, &FirstFit ) );
+
+    // whole file strategies don't split files, and every strategy keeps the blocks of each file
+    for strategy in STRATEGIES {
+      let compacted = compacted( &disk, *strategy );
+      let lens = |disk: &Disk| disk.files().iter().map( |spans| spans.iter().map( |span| span.len ).sum::<usize>() ).collect::<Vec<_>>();
+      assert_eq!( lens(&disk), lens(&compacted), "{strategy}" );
+      assert_eq!( disk.len(), compacted.len() );
+    }
+  }
+
+  #[test]
+  fn test_report() {
+    let disk = parse_input( TEST_INPUT_1 ).unwrap();
+    let expected = DiskReport {
+      blocks: 15, used: 9, free_gaps: 2, largest_free_span: 4, fragmented_files: 0,
+      average_file_offset: 13.0 / 3.0, blocks_moved: 0, checksum: 132,
+    };
+    assert_eq!( expected, disk.report() );
+
+    let report = compacted( &disk, &BlockByBlock ).report();
+    assert_eq!( (0, 6, 1, 5), (report.free_gaps, report.largest_free_span, report.fragmented_files, report.blocks_moved) );
+    assert_eq!( 4.0 / 3.0, report.average_file_offset );
+    assert_eq!( "9 of 15 blocks used, checksum 132\n2 free gap(s), largest free span of 4 block(s)\n\
+      0 fragmented file(s), average file offset 4.3\n0 block(s) moved\n", disk.report().to_string() );
+    assert_eq!( r#"{"blocks": 15, "used": 9, "free_gaps": 0, "largest_free_span": 6, "fragmented_files": 1, "average_file_offset": 1.3333333333333333, "blocks_moved": 5, "checksum": 60}"#,
+      report.json() );
+
+    let disk = parse_input( TEST_INPUT_2 ).unwrap();
+    let reports = compare( &disk, &[&BlockByBlock, &FirstFit] );
+    let summary = reports.0.iter()
+      .map( |(strategy, report)| (strategy.as_str(), report.checksum, report.free_gaps, report.fragmented_files, report.blocks_moved) )
+      .collect::<Vec<_>>();
+    assert_eq!( vec![("block-by-block", 1928, 0, 2, 12), ("first-fit", 2858, 5, 0, 8)], summary );
+
+    let report = Day09::report( &disk, Part::One ).unwrap();
+    let text = report.to_string();
+    assert_eq!( 1 + 1 + STRATEGIES.len(), text.lines().count() );
+    assert!( text.lines().nth( 1 ).unwrap().starts_with( "input " ) );
+    assert!( report.json().starts_with( r#"[{"strategy": "input", "disk": {"blocks": 42, "# ) );
+  }
+
+  #[test]
+  fn test_dense() {
+    // an uncompacted disk gives back its input
+    let disk = parse_input( TEST_INPUT_2 ).unwrap();
+    assert_eq!( TEST_INPUT_2, disk.to_dense().to_string() );
+    assert_eq!( (0..10).collect::<Vec<_>>(), disk.to_dense().ids );
+
+    for strategy in STRATEGIES {
+      let compacted = compacted( &disk, *strategy );
+      let dense = compacted.to_dense();
+      assert_eq!( compacted, Disk::from_dense( &dense ).unwrap(), "{strategy}" );
+    }
+
+    // a file span longer than a digit, after a gap
+    let mut disk = Disk::from_dense( &DenseMap { digits: "9032".to_owned(), ids: vec![0, 0] } ).unwrap();
+    assert_eq!( vec![Span { start: 0, len: 12 }], disk.files()[0] );
+    disk.move_blocks( 0, Span { start: 0, len: 12 }, 2 );
+    assert_eq!( "..000000000000", disk_map(&disk) );
+    let dense = disk.to_dense();
+    assert_eq!( ("02903", vec![0, 0, 0]), (dense.digits.as_str(), dense.ids.clone()) );
+    assert_eq!( disk, Disk::from_dense( &dense ).unwrap() );
+
+    let error = Disk::from_dense( &DenseMap { digits: "123".to_owned(), ids: vec![0] } ).unwrap_err();
+    assert_eq!( (1, 3, "3"), (error.line, error.column, error.text.as_str()) );
+  }
+
+  #[test]
+  fn test_trace() {
+    let mut trace = Trace::new();
+    assert!( Day09::trace( &parse_input(TEST_INPUT_1).unwrap(), Part::One, &mut trace ) );
+    let frames = trace.frames().iter().map( |frame| (frame.event.as_str(), frame.state.as_str()) ).collect::<Vec<_>>();
+    let expected = [
+      ("move 2 block(s) of file 2 from 13 to 1", "022111....222.."),
+      ("move 3 block(s) of file 2 from 10 to 6", "022111222......"),
+    ];
+    assert_eq!( &expected[..], &frames[..] );
+
+    // the part two example, step by step
+    let expected = [
+      "0099.111...2...333.44.5555.6666.777.8888..",
+      "0099.1117772...333.44.5555.6666.....8888..",
+      "0099.111777244.333....5555.6666.....8888..",
+      "00992111777.44.333....5555.6666.....8888..",
+    ];
+    let mut trace = Trace::new();
+    assert!( Day09::trace( &parse_input(TEST_INPUT_2).unwrap(), Part::Two, &mut trace ) );
+    let states = trace.frames().iter().map( |frame| frame.state.as_str() ).collect::<Vec<_>>();
+    assert_eq!( &expected[..], &states[..] );
+    assert_eq!( "move 2 block(s) of file 9 from 40 to 2", trace.frames()[0].event );
+  }
+}
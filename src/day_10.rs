@@ -0,0 +1,183 @@
+use crate::{dag::Dag, grid::Pos, render::{Canvas, Color, Overlay}, search::GridGraph, Mat2D, ParseError, Solution};
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+pub type Grid = Mat2D<u8>;
+
+/// Heights strictly increase along a trail, so the trails form a DAG.
+pub type Trails = Dag<Pos>;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+  const DAY: u8 = 10;
+  type Input = (Grid, Trails);
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+}
+
+fn parse_input( input: &str ) -> Result<(Grid, Trails), ParseError> {
+  let mat = Mat2D::try_from_lines( input, |char| char.to_digit(10).map( |height| height as u8 ), "a height digit" )?;
+  let graph = GridGraph::passable( &mat, |(_, from), (_, to)| *to == *from + 1 );
+  let trails = Dag::new( &graph, mat.iter().map( |(position, _)| Pos::from(position) ) )
+    .expect( "trails should be acyclic as heights strictly increase" );
+  Ok(( mat, trails ))
+}
+
+fn is_trailhead( mat: &Grid ) -> impl Fn( Pos ) -> bool + '_ {
+  |position| mat[position] == 0
+}
+
+fn is_summit( mat: &Grid ) -> impl Fn( Pos ) -> bool + '_ {
+  |position| mat[position] == 9
+}
+
+/// Score and rating of a trailhead : summits reachable from it, and distinct trails leading to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Trailhead {
+  position: Pos,
+  score: usize,
+  rating: usize,
+}
+
+/// Every trailhead of the map, scored and rated in one pass over the trails.
+fn trailheads( mat: &Grid, trails: &Trails ) -> Vec<Trailhead> {
+  trails.source_summaries( is_trailhead(mat), is_summit(mat) ).into_iter()
+    .map( |summary| Trailhead { position: summary.source, score: summary.sinks, rating: summary.paths } )
+    .collect()
+}
+
+/// Trailheads in green and summits in red, cells from which a summit can be reached in yellow.
+fn visualize( (mat, trails): &(Grid, Trails) ) -> Canvas {
+  let on_trail = trails.count_paths( is_summit(mat) ).iter()
+    .filter( |(position, paths)| **paths > 0 && !is_summit(mat)(*position) )
+    .map( |(position, _)| position )
+    .collect::<Vec<_>>();
+  Canvas::with_palette( mat, |_| Some(Color::Grey) )
+    .overlay( &Overlay::path( on_trail ) )
+    .overlay( &Overlay::new( mat.iter().filter( |(_, height)| **height == 0 ).map( |(position, _)| position ), Color::Green ) )
+    .overlay( &Overlay::new( mat.iter().filter( |(_, height)| **height == 9 ).map( |(position, _)| position ), Color::Red ) )
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+mod part_one {
+  use super::*;
+
+  pub(super) fn compute_answer( (mat, trails): &(Grid, Trails) ) -> usize {
+    trailheads( mat, trails ).iter()
+      .map( |trailhead| trailhead.score )
+      .sum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::{TEST_INPUT_1, TEST_INPUT_2};
+
+    #[test]
+    fn test_is_trail() {
+      let (mat, trails) = parse_input( TEST_INPUT_1 ).unwrap();
+      let summits = trails.reachable_sinks( is_summit(&mat) );
+      assert_eq!( Some(&vec![Pos::new(3, 0)]), summits.get( Pos::new(0, 0) ) );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 36;
+      let actual = compute_answer( &parse_input(TEST_INPUT_2).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+mod part_two {
+  use super::*;
+
+  pub(super) fn compute_answer( (mat, trails): &(Grid, Trails) ) -> usize {
+    trailheads( mat, trails ).iter()
+      .map( |trailhead| trailhead.rating )
+      .sum()
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::tests::TEST_INPUT_2;
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 81;
+      let actual = compute_answer( &parse_input(TEST_INPUT_2).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  pub(super) const TEST_INPUT_1: &str = "0123
+1234
+8765
+9876";
+
+  pub(super) const TEST_INPUT_2: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+  #[test]
+  fn test_parse_input() {
+    let (_, trails) = parse_input( TEST_INPUT_1 ).unwrap();
+    assert_eq!( vec![Pos::new(0,1), Pos::new(1,0)], trails.successors( Pos::new(0,0) ).collect::<Vec<_>>() );
+    assert_eq!( vec![Pos::new(3,2), Pos::new(2,1)], trails.successors( Pos::new(2,2) ).collect::<Vec<_>>() );
+  }
+
+  #[test]
+  fn test_trailheads() {
+    let (mat, trails) = parse_input( TEST_INPUT_2 ).unwrap();
+    let trailheads = trailheads( &mat, &trails );
+    assert_eq!( 9, trailheads.len() );
+    assert_eq!( Some(&Trailhead { position: Pos::new(0, 2), score: 5, rating: 20 }),
+      trailheads.iter().find( |trailhead| trailhead.position == Pos::new(0, 2) ) );
+    assert_eq!( 36, trailheads.iter().map( |trailhead| trailhead.score ).sum::<usize>() );
+    assert_eq!( 81, trailheads.iter().map( |trailhead| trailhead.rating ).sum::<usize>() );
+  }
+
+  #[test]
+  fn test_large_map() {
+    // heights rising by one to the right and down, so a trail only ever goes right or down, to the 10 summits
+    // 9 steps away, along the 9-choose-i paths to each one : the ones inside the map count
+    let size = 1000;
+    let input = (0..size)
+      .map( |row| (0..size).map( |col| char::from( b'0' + ((row + col) % 10) as u8 ) ).collect::<String>() )
+      .collect::<Vec<_>>()
+      .join( "\n" );
+    let choose = [1, 9, 36, 84, 126, 126, 84, 36, 9, 1];
+    let (mut score, mut rating) = (0, 0);
+    for (row, col) in (0..size).flat_map( |row| (0..size).map( move |col| (row, col) ) ).filter( |(row, col)| (row + col) % 10 == 0 ) {
+      for down in (0..10).filter( |down| row + down < size && col + 9 - down < size ) {
+        score += 1;
+        rating += choose[ down ];
+      }
+    }
+
+    let input = parse_input( &input ).unwrap();
+    assert_eq!( score, part_one::compute_answer( &input ) );
+    assert_eq!( rating, part_two::compute_answer( &input ) );
+  }
+}
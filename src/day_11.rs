@@ -0,0 +1,233 @@
+use std::collections::{BTreeMap, HashMap};
+use crate::{render::{Canvas, Color, Overlay}, trace::{Recorder, Trace}, ParseError, Part, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+  const DAY: u8 = 11;
+  type Input = Vec<usize>;
+  type Answer = usize;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError> { parse_input( input ) }
+  fn part_one( input: &Self::Input ) -> Self::Answer { part_one::compute_answer( input ) }
+  fn part_two( input: &Self::Input ) -> Self::Answer { part_two::compute_answer( input ) }
+  fn trace( input: &Self::Input, part: Part, trace: &mut Trace<String> ) -> bool {
+    let blinks = match part {
+      Part::One => 25,
+      Part::Two => 75,
+    };
+    blink_counts( input, blinks, trace );
+    true
+  }
+  fn visualize( input: &Self::Input ) -> Option<Canvas> { Some( visualize(input) ) }
+}
+
+fn parse_input( input: &str ) -> Result<Vec<usize>, ParseError> {
+  input.split_whitespace()
+    .map( |stone| ParseError::parse( input, stone, "a stone number" ) )
+    .collect()
+}
+
+/// Blinks `blinks` times keeping a count per distinct stone, the stones being recorded as `stone x count` after each blink.
+fn blink_counts( stones: &[usize], blinks: usize, recorder: &mut impl Recorder<String> ) -> usize {
+  let mut counts = HashMap::<usize, usize>::new();
+  for stone in stones {
+    *counts.entry( *stone ).or_default() += 1;
+  }
+  for blink in 1..=blinks {
+    let mut next = HashMap::new();
+    for (stone, count) in counts {
+      for stone in part_one::blink( stone ) {
+        *next.entry( stone ).or_default() += count;
+      }
+    }
+    counts = next;
+    recorder.record( || {
+      let sorted = counts.iter().collect::<BTreeMap<_,_>>();
+      let event = format!( "blink {blink}: {} stones, {} distinct", sorted.values().copied().sum::<usize>(), sorted.len() );
+      let state = sorted.iter().map( |(stone, count)| format!( "{stone} x {count}" ) ).collect::<Vec<_>>().join( "\n" );
+      (event, state + "\n")
+    });
+  }
+  counts.values().sum()
+}
+
+/// The stones after each blink for as long as they fit in `VIEW_WIDTH` columns : zeros in cyan and stones about to
+/// split in yellow.
+fn visualize( stones: &[usize] ) -> Canvas {
+  const VIEW_WIDTH: usize = 100;
+  let mut rows = Vec::new();
+  let mut stones = stones.to_vec();
+  loop {
+    let labels = stones.iter().map( usize::to_string ).collect::<Vec<_>>();
+    let width = labels.iter().map( String::len ).sum::<usize>() + labels.len().saturating_sub( 1 );
+    if width > VIEW_WIDTH && !rows.is_empty() {
+      break;
+    }
+    rows.push( labels );
+    stones = stones.into_iter().flat_map( part_one::blink ).collect();
+  }
+  let text = rows.iter().map( |labels| labels.join( " " ) + "\n" ).collect::<String>();
+  let mut canvas = Canvas::text( &text );
+  for (row, labels) in rows.iter().enumerate() {
+    let mut start = 0;
+    for label in labels {
+      let color = if label == "0" {
+        Some(Color::Cyan)
+      } else if label.len().is_multiple_of( 2 ) {
+        Some(Color::Yellow)
+      } else {
+        None
+      };
+      if let Some(color) = color {
+        canvas = canvas.overlay( &Overlay::span( row, start..start + label.len(), color ) );
+      }
+      start += label.len() + 1;
+    }
+  }
+  canvas
+}
+
+mod part_one {
+  use itertools::Either;
+
+  pub(super) fn compute_answer( stones: &[usize] ) -> usize {
+    let mut stones = stones.to_vec();
+    for _ in 0..25 {
+      stones = stones.into_iter().flat_map( blink ).collect();
+    }
+    stones.len()
+  }
+
+  pub(super) fn blink( stone: usize ) -> impl Iterator<Item=usize> {
+    if stone == 0 {
+      return Either::Left( std::iter::once(1_usize) );
+    }
+    let num_digits = stone.ilog10() + 1;
+    if num_digits.is_multiple_of( 2 ) {
+      let split_at = 10_usize.pow( num_digits / 2 );
+      let left = stone / split_at;
+      let right = stone - left*split_at;
+      return Either::Right( [left, right].into_iter() );
+    }
+    Either::Left( std::iter::once(stone*2024) )
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    use super::super::{parse_input, tests::TEST_INPUT};
+
+    #[test]
+    fn test_blink() {
+      let input = parse_input( TEST_INPUT ).unwrap();
+      let expected: &[usize] = &[ 253000, 1, 7 ];
+      let actual = input.into_iter().flat_map( blink ).collect::<Vec<_>>();
+      assert_eq!( expected, actual );
+
+      let expected: &[usize] = &[ 253, 0, 2024, 14168 ];
+      let actual = actual.into_iter().flat_map( blink ).collect::<Vec<_>>();
+      assert_eq!( expected, actual );
+
+      let expected: &[usize] = &[ 512072, 1, 20, 24, 28676032 ];
+      let actual = actual.into_iter().flat_map( blink ).collect::<Vec<_>>();
+      assert_eq!( expected, actual );
+
+      let expected: &[usize] = &[ 512, 72, 2024, 2, 0, 2, 4, 2867, 6032 ];
+      let actual = actual.into_iter().flat_map( blink ).collect::<Vec<_>>();
+      assert_eq!( expected, actual );
+
+      let expected: &[usize] = &[ 1036288, 7, 2, 20, 24, 4048, 1, 4048, 8096, 28, 67, 60, 32 ];
+      let actual = actual.into_iter().flat_map( blink ).collect::<Vec<_>>();
+      assert_eq!( expected, actual );
+
+      let expected: &[usize] = &[ 2097446912, 14168, 4048, 2, 0, 2, 4, 40, 48, 2024, 40, 48, 80, 96, 2, 8, 6, 7, 6, 0, 3, 2 ];
+      let actual = actual.into_iter().flat_map( blink ).collect::<Vec<_>>();
+      assert_eq!( expected, actual );
+    }
+
+    #[test]
+    fn test_compute_answer() {
+      let expected = 55312;
+      let actual = compute_answer( &parse_input(TEST_INPUT).unwrap() );
+      assert_eq!( expected, actual );
+    }
+  }
+}
+
+mod part_two {
+  use std::collections::HashMap;
+  use itertools::Either;
+
+  const MAX_DEPTH: usize = 75;
+  type Cache = HashMap<(usize,usize), usize>;
+
+  pub(super) fn compute_answer( stones: &[usize] ) -> usize {
+    fn inner( stone: usize, depth: usize, cache: &mut Cache ) -> usize {
+      if depth == MAX_DEPTH { return 1; }
+      if let Some( count ) = cache.get( &(stone, MAX_DEPTH-depth) ) { return *count; }
+      let count = match blink( stone ) {
+        Either::Left( stone ) => inner( stone, depth+1, cache ),
+        Either::Right( [left, right] ) => inner( left, depth+1, cache ) + inner( right, depth+1, cache ),
+      };
+      cache.insert( (stone, MAX_DEPTH-depth), count );
+      count
+    }
+
+    let mut cache = Cache::new();
+    stones.iter()
+      .map( |stone| inner( *stone, 0, &mut cache ) )
+      .sum()
+  }
+
+  fn blink( stone: usize ) -> Either<usize, [usize; 2]> {
+    if stone == 0 {
+      return Either::Left( 1_usize );
+    }
+    let num_digits = stone.ilog10() + 1;
+    if num_digits.is_multiple_of( 2 ) {
+      let split_at = 10_usize.pow( num_digits / 2 );
+      let left = stone / split_at;
+      let right = stone - left*split_at;
+      return Either::Right( [left, right] );
+    }
+    Either::Left( stone * 2024 )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  pub(super) const TEST_INPUT: &str = "125 17";
+
+  #[test]
+  fn test_visualize() {
+    let canvas = visualize( &parse_input( TEST_INPUT ).unwrap() );
+    let text = canvas.to_string();
+    let lines = text.lines().map( str::trim_end ).collect::<Vec<_>>();
+    assert_eq!( "125 17", lines[0] );
+    assert_eq!( "253000 1 7", lines[1] );
+    assert_eq!( 7, lines.len() );
+
+    let ansi = canvas.ansi().to_string();
+    assert_eq!( Some("253 \x1b[36m0\x1b[0m \x1b[33m2024\x1b[0m 14168"), ansi.lines().nth( 2 ).map( str::trim_end ) );
+  }
+
+  #[test]
+  fn test_parse_input() {
+    let expected: &[usize] = &[ 125, 17 ];
+    let actual = parse_input( TEST_INPUT ).unwrap();
+    assert_eq!( expected, actual );
+  }
+
+  #[test]
+  fn test_trace() {
+    let mut trace = Trace::new();
+    assert!( Day11::trace( &parse_input(TEST_INPUT).unwrap(), Part::One, &mut trace ) );
+    assert_eq!( 25, trace.frames().len() );
+    assert_eq!( "1 x 1\n7 x 1\n253000 x 1\n", trace.frames()[0].state );
+    assert!( trace.frames()[24].event.starts_with( "blink 25: 55312 stones" ) );
+    assert_eq!( 55312, blink_counts( &parse_input(TEST_INPUT).unwrap(), 25, &mut () ) );
+  }
+}
@@ -0,0 +1,299 @@
+use std::ops;
+use crate::Mat2D;
+
+// Grid geometry shared by the days working on a Mat2D : rows grow southward, cols grow eastward.
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Signed offset between two cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vec2 {
+  pub row: isize,
+  pub col: isize,
+}
+
+impl Vec2 {
+  pub const fn new( row: isize, col: isize ) -> Self {
+    Self { row, col }
+  }
+}
+
+impl ops::Add for Vec2 {
+  type Output = Self;
+
+  fn add( self, other: Self ) -> Self {
+    Self::new( self.row + other.row, self.col + other.col )
+  }
+}
+
+impl ops::Sub for Vec2 {
+  type Output = Self;
+
+  fn sub( self, other: Self ) -> Self {
+    Self::new( self.row - other.row, self.col - other.col )
+  }
+}
+
+impl ops::Neg for Vec2 {
+  type Output = Self;
+
+  fn neg( self ) -> Self {
+    Self::new( -self.row, -self.col )
+  }
+}
+
+impl ops::Mul<isize> for Vec2 {
+  type Output = Self;
+
+  fn mul( self, factor: isize ) -> Self {
+    Self::new( self.row * factor, self.col * factor )
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// The 4 orthogonal directions, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+  North,
+  East,
+  South,
+  West,
+}
+
+impl Direction {
+  pub const DIRECTIONS: [Self; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+  ];
+
+  pub fn turn_right( self ) -> Self {
+    match self {
+      Direction::North => Direction::East,
+      Direction::East => Direction::South,
+      Direction::South => Direction::West,
+      Direction::West => Direction::North,
+    }
+  }
+
+  pub fn turn_left( self ) -> Self {
+    self.turn_around().turn_right()
+  }
+
+  pub fn turn_around( self ) -> Self {
+    self.turn_right().turn_right()
+  }
+
+  pub fn offset( self ) -> Vec2 {
+    match self {
+      Direction::North => Vec2::new( -1, 0 ),
+      Direction::East => Vec2::new( 0, 1 ),
+      Direction::South => Vec2::new( 1, 0 ),
+      Direction::West => Vec2::new( 0, -1 ),
+    }
+  }
+}
+
+impl From<Direction> for Vec2 {
+  fn from( direction: Direction ) -> Self {
+    direction.offset()
+  }
+}
+
+/// The 8 orthogonal and diagonal directions, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+  North,
+  NorthEast,
+  East,
+  SouthEast,
+  South,
+  SouthWest,
+  West,
+  NorthWest,
+}
+
+impl Direction8 {
+  pub const DIRECTIONS: [Self; 8] = [
+    Direction8::North,
+    Direction8::NorthEast,
+    Direction8::East,
+    Direction8::SouthEast,
+    Direction8::South,
+    Direction8::SouthWest,
+    Direction8::West,
+    Direction8::NorthWest,
+  ];
+
+  fn rotate( self, eighths: usize ) -> Self {
+    Self::DIRECTIONS[ (self as usize + eighths) % 8 ]
+  }
+
+  /// Quarter turn clockwise, use [`Direction8::rotate_right`] for an eighth of a turn.
+  pub fn turn_right( self ) -> Self {
+    self.rotate( 2 )
+  }
+
+  pub fn turn_left( self ) -> Self {
+    self.rotate( 6 )
+  }
+
+  pub fn turn_around( self ) -> Self {
+    self.rotate( 4 )
+  }
+
+  /// Eighth of a turn clockwise.
+  pub fn rotate_right( self ) -> Self {
+    self.rotate( 1 )
+  }
+
+  pub fn rotate_left( self ) -> Self {
+    self.rotate( 7 )
+  }
+
+  pub fn offset( self ) -> Vec2 {
+    match self {
+      Direction8::North => Vec2::new( -1, 0 ),
+      Direction8::NorthEast => Vec2::new( -1, 1 ),
+      Direction8::East => Vec2::new( 0, 1 ),
+      Direction8::SouthEast => Vec2::new( 1, 1 ),
+      Direction8::South => Vec2::new( 1, 0 ),
+      Direction8::SouthWest => Vec2::new( 1, -1 ),
+      Direction8::West => Vec2::new( 0, -1 ),
+      Direction8::NorthWest => Vec2::new( -1, -1 ),
+    }
+  }
+}
+
+impl From<Direction8> for Vec2 {
+  fn from( direction: Direction8 ) -> Self {
+    direction.offset()
+  }
+}
+
+impl From<Direction> for Direction8 {
+  fn from( direction: Direction ) -> Self {
+    match direction {
+      Direction::North => Direction8::North,
+      Direction::East => Direction8::East,
+      Direction::South => Direction8::South,
+      Direction::West => Direction8::West,
+    }
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// A cell of a grid, convertible from and to the `(row, col)` tuples used by [`Mat2D`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Pos {
+  pub row: usize,
+  pub col: usize,
+}
+
+impl Pos {
+  pub const fn new( row: usize, col: usize ) -> Self {
+    Self { row, col }
+  }
+
+  /// Moves by `offset`, `None` if that would leave the non-negative quadrant.
+  pub fn offset( self, offset: impl Into<Vec2> ) -> Option<Self> {
+    let offset = offset.into();
+    let row = self.row.checked_add_signed( offset.row )?;
+    let col = self.col.checked_add_signed( offset.col )?;
+    Some( Self::new(row, col) )
+  }
+
+  /// Moves by `offset`, `None` if that would leave `mat`.
+  pub fn step<T>( self, offset: impl Into<Vec2>, mat: &Mat2D<T> ) -> Option<Self> {
+    self.offset( offset )
+      .filter( |pos| mat.contains(*pos) )
+  }
+}
+
+impl ops::Sub for Pos {
+  type Output = Vec2;
+
+  fn sub( self, other: Self ) -> Vec2 {
+    Vec2::new( self.row as isize - other.row as isize, self.col as isize - other.col as isize )
+  }
+}
+
+impl From<(usize, usize)> for Pos {
+  fn from( (row, col): (usize, usize) ) -> Self {
+    Self::new( row, col )
+  }
+}
+
+impl From<Pos> for (usize, usize) {
+  fn from( pos: Pos ) -> Self {
+    (pos.row, pos.col)
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+impl<T> Mat2D<T> {
+  fn neighbours<I>( &self, position: (usize, usize), offsets: I ) -> impl Iterator<Item=((usize,usize), &T)> + use<'_, T, I>
+    where I: IntoIterator<Item=Vec2> {
+    offsets.into_iter()
+      .filter_map( move |offset| Pos::from( position ).step( offset, self ) )
+      .map( |pos| (pos.into(), &self[pos]) )
+  }
+
+  /// Von Neumann neighbourhood : the up to 4 orthogonal neighbours, in [`Direction::DIRECTIONS`] order.
+  pub fn neighbours_4( &self, position: impl Into<(usize, usize)> ) -> impl Iterator<Item=((usize,usize), &T)> {
+    self.neighbours( position.into(), Direction::DIRECTIONS.map( Direction::offset ) )
+  }
+
+  /// Moore neighbourhood : the up to 8 orthogonal and diagonal neighbours, in [`Direction8::DIRECTIONS`] order.
+  pub fn neighbours_8( &self, position: impl Into<(usize, usize)> ) -> impl Iterator<Item=((usize,usize), &T)> {
+    self.neighbours( position.into(), Direction8::DIRECTIONS.map( Direction8::offset ) )
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_turns() {
+    assert_eq!( Direction::East, Direction::North.turn_right() );
+    assert_eq!( Direction::West, Direction::North.turn_left() );
+    assert_eq!( Direction::South, Direction::North.turn_around() );
+    assert_eq!( Direction8::SouthWest, Direction8::NorthWest.turn_left() );
+    assert_eq!( Direction8::NorthEast, Direction8::NorthWest.turn_right() );
+    assert_eq!( Direction8::North, Direction8::NorthWest.rotate_right() );
+    assert_eq!( Direction8::SouthEast, Direction8::NorthWest.turn_around() );
+    for direction in Direction8::DIRECTIONS {
+      assert_eq!( -direction.offset(), direction.turn_around().offset() );
+    }
+  }
+
+  #[test]
+  fn test_pos() {
+    let mat = Mat2D::new( 3, 4, '.' );
+    let pos = Pos::new( 0, 3 );
+    assert_eq!( None, pos.step( Direction::North, &mat ) );
+    assert_eq!( None, pos.step( Direction::East, &mat ) );
+    assert_eq!( Some(Pos::new(1, 2)), pos.step( Direction8::SouthWest, &mat ) );
+    assert_eq!( Some(Pos::new(0, 4)), pos.offset( Direction::East ) );
+    assert_eq!( Vec2::new(2, -3), Pos::new(2, 0) - pos );
+    assert_eq!( Some(Pos::new(2, 0)), pos.step( Vec2::new(2, -3), &mat ) );
+    assert_eq!( Vec2::new(4, -6), (Pos::new(2, 0) - pos) * 2 );
+  }
+
+  #[test]
+  fn test_neighbours() {
+    let mat = Mat2D::from_vec( 3, 3, (0..9).collect() ).unwrap();
+    let neighbours = |iter: &mut dyn Iterator<Item=((usize,usize), &i32)>| iter.map( |(_, value)| *value ).collect::<Vec<_>>();
+    assert_eq!( vec![1, 5, 7, 3], neighbours( &mut mat.neighbours_4((1,1)) ) );
+    assert_eq!( vec![1, 2, 5, 8, 7, 6, 3, 0], neighbours( &mut mat.neighbours_8((1,1)) ) );
+    assert_eq!( vec![1, 3], neighbours( &mut mat.neighbours_4((0,0)) ) );
+    assert_eq!( vec![1, 4, 3], neighbours( &mut mat.neighbours_8(Pos::new(0, 0)) ) );
+  }
+}
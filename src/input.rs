@@ -0,0 +1,205 @@
+use std::{env, fmt, fs, io::{self, IsTerminal, Read}, path::PathBuf};
+
+// Inputs are personal to each account and kept out of the repository, so they are looked up at run time, in order :
+//   1. an explicit path (`--input <path>`, `-` meaning stdin)
+//   2. `$AOC_INPUT_DIR/day_NN.txt`
+//   3. `inputs/2024/day_NN.txt`, relative to the working directory
+//   4. stdin, when it is piped rather than a terminal
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const DEFAULT_INPUT_DIR: &str = "inputs/2024";
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+  File(PathBuf),
+  Stdin,
+}
+
+impl fmt::Display for Source {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    match self {
+      Source::File(path) => write!( f, "{}", path.display() ),
+      Source::Stdin => write!( f, "<stdin>" ),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct Input {
+  pub source: Source,
+  pub text: String,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+  NotFound { day: u8, tried: Vec<Source> },
+  Io { source: Source, error: io::Error },
+}
+
+impl fmt::Display for InputError {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    match self {
+      InputError::NotFound { day, tried } => {
+        writeln!( f, "no input found for day {day}, tried :" )?;
+        for source in tried {
+          writeln!( f, "  - {source}" )?;
+        }
+        write!( f, "hint: save your puzzle input as {DEFAULT_INPUT_DIR}/{}, set {INPUT_DIR_VAR} or pass --input <path>", file_name(*day) )
+      },
+      InputError::Io { source, error } => write!( f, "failed to read input from {source}: {error}" ),
+    }
+  }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn file_name( day: u8 ) -> String {
+  format!( "day_{day:02}.txt" )
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default)]
+pub struct InputResolver {
+  path: Option<PathBuf>,
+  input_dir: Option<PathBuf>,
+  default_dir: Option<PathBuf>,
+  stdin: bool,
+}
+
+impl InputResolver {
+  /// Resolver reading `AOC_INPUT_DIR` from the environment, falling back on `inputs/2024` then on a piped stdin.
+  pub fn from_env() -> Self {
+    Self {
+      path: None,
+      input_dir: env::var_os( INPUT_DIR_VAR ).map( PathBuf::from ),
+      default_dir: Some( PathBuf::from(DEFAULT_INPUT_DIR) ),
+      stdin: !io::stdin().is_terminal(),
+    }
+  }
+
+  /// Explicit path taking precedence over every other location, `-` reads stdin.
+  pub fn with_path( mut self, path: impl Into<PathBuf> ) -> Self {
+    self.path = Some( path.into() );
+    self
+  }
+
+  pub fn with_input_dir( mut self, dir: impl Into<PathBuf> ) -> Self {
+    self.input_dir = Some( dir.into() );
+    self
+  }
+
+  pub fn with_default_dir( mut self, dir: impl Into<PathBuf> ) -> Self {
+    self.default_dir = Some( dir.into() );
+    self
+  }
+
+  pub fn with_stdin( mut self, stdin: bool ) -> Self {
+    self.stdin = stdin;
+    self
+  }
+
+  /// Every location that would be tried for `day`, in order.
+  pub fn candidates( &self, day: u8 ) -> Vec<Source> {
+    if let Some(path) = &self.path {
+      return match path.as_os_str().to_str() {
+        Some("-") => vec!( Source::Stdin ),
+        _ => vec!( Source::File(path.clone()) ),
+      };
+    }
+
+    let mut candidates = [&self.input_dir, &self.default_dir].into_iter()
+      .flatten()
+      .map( |dir| Source::File( dir.join(file_name(day)) ) )
+      .collect::<Vec<_>>();
+    if self.stdin {
+      candidates.push( Source::Stdin );
+    }
+    candidates
+  }
+
+  pub fn resolve( &self, day: u8 ) -> Result<Input, InputError> {
+    let candidates = self.candidates( day );
+    let explicit = self.path.is_some();
+    for source in &candidates {
+      match read( source ) {
+        Ok(text) => return Ok( Input { source: source.clone(), text } ),
+        Err(error) if explicit || error.kind() != io::ErrorKind::NotFound =>
+          return Err( InputError::Io { source: source.clone(), error } ),
+        Err(_) => continue,
+      }
+    }
+    Err( InputError::NotFound { day, tried: candidates } )
+  }
+}
+
+fn read( source: &Source ) -> io::Result<String> {
+  match source {
+    Source::File(path) => fs::read_to_string( path ),
+    Source::Stdin => {
+      let mut text = String::new();
+      io::stdin().read_to_string( &mut text )?;
+      match text.is_empty() {
+        true => Err( io::Error::new( io::ErrorKind::NotFound, "stdin is empty" ) ),
+        false => Ok( text ),
+      }
+    },
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_dir( name: &str ) -> PathBuf {
+    let dir = env::temp_dir().join( format!( "aoc-2024-{name}-{}", std::process::id() ) );
+    fs::create_dir_all( &dir ).unwrap();
+    dir
+  }
+
+  #[test]
+  fn test_candidates() {
+    let resolver = InputResolver::default()
+      .with_input_dir( "env" )
+      .with_default_dir( "inputs/2024" )
+      .with_stdin( true );
+    let expected = vec!(
+      Source::File( PathBuf::from("env/day_07.txt") ),
+      Source::File( PathBuf::from("inputs/2024/day_07.txt") ),
+      Source::Stdin,
+    );
+    assert_eq!( expected, resolver.candidates(7) );
+
+    let resolver = resolver.with_path( "-" );
+    assert_eq!( vec!( Source::Stdin ), resolver.candidates(7) );
+  }
+
+  #[test]
+  fn test_resolve() {
+    let env_dir = temp_dir( "env" );
+    let default_dir = temp_dir( "default" );
+    fs::write( default_dir.join("day_01.txt"), "default" ).unwrap();
+    fs::write( default_dir.join("day_02.txt"), "default" ).unwrap();
+    fs::write( env_dir.join("day_02.txt"), "env" ).unwrap();
+
+    let resolver = InputResolver::default()
+      .with_input_dir( &env_dir )
+      .with_default_dir( &default_dir );
+    assert_eq!( "default", resolver.resolve(1).unwrap().text );
+    assert_eq!( "env", resolver.resolve(2).unwrap().text );
+
+    let error = resolver.resolve( 3 ).unwrap_err();
+    assert!( matches!( &error, InputError::NotFound { day: 3, tried } if tried.len() == 2 ) );
+    println!( "{error}" );
+
+    let error = resolver.with_path( env_dir.join("missing.txt") ).resolve( 2 ).unwrap_err();
+    assert!( matches!( error, InputError::Io { .. } ) );
+
+    fs::remove_dir_all( env_dir ).unwrap();
+    fs::remove_dir_all( default_dir ).unwrap();
+  }
+}
@@ -1,8 +1,234 @@
-use std::{fs, io, path};
+use std::{fmt, fs, io, path, str};
 
-// to run tests for examples : cargo test --example day_01 -- --nocapture
-// cargo test --example day_01 part_one -- --nocapture
-// cargo test --example '*'
+// to run tests for a day : cargo test day_01 -- --nocapture
+// cargo test day_01::part_one -- --nocapture
+// cargo test
+// to run solutions : cargo run --bin aoc -- run --day 1
+// to spread independent work over all cores : cargo run --release --features parallel --bin aoc -- run
+
+mod mat2d;
+pub use mat2d::Mat2D;
+
+pub mod answers;
+pub mod bench;
+pub mod bitset;
+pub mod dag;
+pub mod grid;
+pub mod input;
+pub mod parallel;
+pub mod render;
+pub mod report;
+pub mod search;
+pub mod trace;
+
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// A day's puzzle : parse the input once, then compute each part from the parsed input.
+pub trait Solution {
+  const DAY: u8;
+  type Input;
+  type Answer: fmt::Display;
+
+  fn parse( input: &str ) -> Result<Self::Input, ParseError>;
+  fn part_one( input: &Self::Input ) -> Self::Answer;
+  fn part_two( input: &Self::Input ) -> Self::Answer;
+
+  /// Drawing of the parsed input and of what the parts found in it, for `aoc run --visualize`.
+  fn visualize( _input: &Self::Input ) -> Option<render::Canvas> { None }
+
+  /// Records the steps of the part's simulation into `trace` for `aoc trace`, false if the part isn't traceable.
+  fn trace( _input: &Self::Input, _part: Part, _trace: &mut trace::Trace<String> ) -> bool { false }
+
+  /// Details of how the part found its answer, for `aoc report`.
+  fn report( _input: &Self::Input, _part: Part ) -> Option<report::BoxedReport> { None }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const PARTS: [Self; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    match self {
+      Part::One => write!( f, "1" ),
+      Part::Two => write!( f, "2" ),
+    }
+  }
+}
+
+impl str::FromStr for Part {
+  type Err = String;
+
+  fn from_str( input: &str ) -> Result<Self, Self::Err> {
+    match input {
+      "1" => Ok( Part::One ),
+      "2" => Ok( Part::Two ),
+      _ => Err( format!( "invalid part '{input}', expected 1 or 2" ) ),
+    }
+  }
+}
+
+pub type Answers = Vec<(Part, String)>;
+
+/// Type-erased handle on a [`Solution`], so every day can be registered in [`DAYS`].
+#[derive(Clone, Copy)]
+pub struct Day {
+  pub number: u8,
+  run: fn( &str, &[Part] ) -> Result<Answers, ParseError>,
+  bench: fn( &str, usize ) -> Result<bench::DayBench, ParseError>,
+  visualize: fn( &str ) -> Result<Option<render::Canvas>, ParseError>,
+  trace: fn( &str, Part, &mut trace::Trace<String> ) -> Result<bool, ParseError>,
+  report: fn( &str, Part ) -> Result<Option<report::BoxedReport>, ParseError>,
+}
+
+impl Day {
+  pub const fn of<S: Solution>() -> Self {
+    Self {
+      number: S::DAY,
+      run: run::<S>,
+      bench: bench::bench::<S>,
+      visualize: |input| Ok( S::visualize( &S::parse(input)? ) ),
+      trace: |input, part, trace| Ok( S::trace( &S::parse(input)?, part, trace ) ),
+      report: |input, part| Ok( S::report( &S::parse(input)?, part ) ),
+    }
+  }
+
+  pub fn find( number: u8 ) -> Option<Self> {
+    DAYS.iter().copied()
+      .find( |day| day.number == number )
+  }
+
+  /// Parses `input` once and computes the requested `parts`, in order.
+  pub fn run( &self, input: &str, parts: &[Part] ) -> Result<Answers, ParseError> {
+    (self.run)( input, parts )
+  }
+
+  /// Times parse, part one and part two separately over `runs` repetitions.
+  pub fn bench( &self, input: &str, runs: usize ) -> Result<bench::DayBench, ParseError> {
+    (self.bench)( input, runs )
+  }
+
+  /// Parses `input` and draws it, `None` if the day has no visualization.
+  pub fn visualize( &self, input: &str ) -> Result<Option<render::Canvas>, ParseError> {
+    (self.visualize)( input )
+  }
+
+  /// Parses `input` and records the `part` simulation into `trace`, false if the part isn't traceable.
+  pub fn trace( &self, input: &str, part: Part, trace: &mut trace::Trace<String> ) -> Result<bool, ParseError> {
+    (self.trace)( input, part, trace )
+  }
+
+  /// Parses `input` and details how `part` is solved, `None` if the day has no report for it.
+  pub fn report( &self, input: &str, part: Part ) -> Result<Option<report::BoxedReport>, ParseError> {
+    (self.report)( input, part )
+  }
+}
+
+fn run<S: Solution>( input: &str, parts: &[Part] ) -> Result<Answers, ParseError> {
+  let input = S::parse( input )?;
+  let answers = parts.iter()
+    .map( |part| match part {
+      Part::One => (*part, S::part_one( &input ).to_string()),
+      Part::Two => (*part, S::part_two( &input ).to_string()),
+    })
+    .collect();
+  Ok( answers )
+}
+
+pub const DAYS: &[Day] = &[
+  Day::of::<day_01::Day01>(),
+  Day::of::<day_02::Day02>(),
+  Day::of::<day_03::Day03>(),
+  Day::of::<day_04::Day04>(),
+  Day::of::<day_05::Day05>(),
+  Day::of::<day_06::Day06>(),
+  Day::of::<day_07::Day07>(),
+  Day::of::<day_08::Day08>(),
+  Day::of::<day_09::Day09>(),
+  Day::of::<day_10::Day10>(),
+  Day::of::<day_11::Day11>(),
+];
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Where and why a day's input failed to parse. Line and column are 1-based, the column counts chars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  pub line: usize,
+  pub column: usize,
+  pub text: String,
+  pub expected: String,
+  source_line: String,
+}
+
+impl ParseError {
+  /// Error on `span`, which must be a subslice of `input` so its line and column can be recovered.
+  /// An empty span at the end of a line reports something missing there.
+  pub fn new( input: &str, span: &str, expected: impl Into<String> ) -> Self {
+    let offset = (span.as_ptr() as usize).checked_sub( input.as_ptr() as usize )
+      .filter( |offset| *offset <= input.len() && input.is_char_boundary(*offset) )
+      .unwrap_or( input.len() );
+    let before = &input[ ..offset ];
+    let line_start = before.rfind( '\n' ).map_or( 0, |idx| idx+1 );
+    let line_end = input[ offset.. ].find( '\n' ).map_or( input.len(), |idx| offset+idx );
+    Self {
+      line: before.matches( '\n' ).count() + 1,
+      column: before[ line_start.. ].chars().count() + 1,
+      text: span.to_owned(),
+      expected: expected.into(),
+      source_line: input[ line_start..line_end ].trim_end_matches( '\r' ).to_owned(),
+    }
+  }
+
+  /// Parses `token` (a subslice of `input`) as a `T`, reporting `expected` on failure.
+  pub fn parse<T: str::FromStr>( input: &str, token: &str, expected: &str ) -> Result<T, Self> {
+    token.parse().map_err( |_| Self::new( input, token, expected ) )
+  }
+
+  /// Moves an error computed on a single line to the `line`-th line of the whole input.
+  pub fn on_line( mut self, line: usize ) -> Self {
+    self.line += line - 1;
+    self
+  }
+
+  /// Error message followed by the offending line, with carets under the offending text.
+  pub fn render( &self ) -> String {
+    let gutter = self.line.to_string().len();
+    let padding = self.source_line.chars().take( self.column-1 ).map( |char| if char == '\t' { '\t' } else { ' ' } ).collect::<String>();
+    let carets = "^".repeat( self.text.chars().count().max(1) );
+    format!( "{self}\n{:gutter$} |\n{} | {}\n{:gutter$} | {padding}{carets}", "", self.line, self.source_line, "" )
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    write!( f, "line {}, column {}: expected {}, ", self.line, self.column, self.expected )?;
+    match self.text.as_str() {
+      "" => write!( f, "found nothing" ),
+      text => write!( f, "found '{text}'" ),
+    }
+  }
+}
+
+impl std::error::Error for ParseError {}
 
 // ---------------------------------------------------------------------------------------------------------------------------------
 
@@ -39,39 +265,6 @@ impl<T> Deref for Lazy<T> {
   }
 }
 
-// ---------------------------------------------------------------------------------------------------------------------------------
-
-#[derive(Debug)]
-pub struct Mat2D<T> {
-  data: Vec<Vec<T>>,
-}
-
-impl<T, II> FromIterator<II> for Mat2D<T> where II: IntoIterator<Item=T> {
-  fn from_iter<III>( iter: III ) -> Self where III: IntoIterator<Item=II> {
-    let data = iter.into_iter()
-      .map( |line| line.into_iter().collect() )
-      .collect();
-    Self { data }
-  }
-}
-
-impl<T> Mat2D<T> {
-  pub fn get( &self, (row, col): (usize, usize) ) -> Option<&T> {
-    self.data.get( row )
-      .and_then( |row| row.get(col) )
-  }
-
-  pub fn iter( &self ) -> impl Iterator<Item=((usize,usize), &T)> {
-    self.data.iter().enumerate()
-      .flat_map( |(row_idx, row)|
-        row.iter().enumerate()
-          .map( move |(col_idx, value)|
-            ((row_idx,col_idx), value)
-      ))
-  }
-}
-
-
 // =================================================================================================================================
 
 #[cfg(test)]
@@ -80,24 +273,18 @@ mod tests {
   use super::*;
 
   #[test]
-  fn test_mat2d() {
-    let data =
-     "aaaaaaaa
-bbbbbbbb
-cccccccc
-dddddddd
-eeeeeeee
-ffffffff
-gggggggg
-hhhhhhhh";
-
-    let mat = data.lines()
-      .map( str::chars )
-      .collect::<Mat2D<_>>();
-
-    assert_eq!( Some(&'a'), mat.get((0,0)) );
-    assert_eq!( Some(&'a'), mat.get((0,7)) );
-    assert_eq!( Some(&'h'), mat.get((7,0)) );
-    assert_eq!( Some(&'h'), mat.get((7,7)) );
+  fn test_parse_error() {
+    let input = "12 34\n56 7x\n";
+    let token = input.lines().nth( 1 ).unwrap().split_whitespace().nth( 1 ).unwrap();
+    let error = ParseError::parse::<usize>( input, token, "a number" ).unwrap_err();
+    assert_eq!( (2, 4), (error.line, error.column) );
+    assert_eq!( "7x", error.text );
+    assert_eq!( "line 2, column 4: expected a number, found '7x'", error.to_string() );
+    assert_eq!( "line 2, column 4: expected a number, found '7x'\n  |\n2 | 56 7x\n  |    ^^", error.render() );
+
+    let line = input.lines().next().unwrap();
+    let error = ParseError::new( line, &line[line.len()..], "a third number" ).on_line( 3 );
+    assert_eq!( (3, 6), (error.line, error.column) );
+    assert_eq!( "line 3, column 6: expected a third number, found nothing", error.to_string() );
   }
 }
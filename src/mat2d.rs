@@ -0,0 +1,404 @@
+use std::{mem, ops};
+use crate::ParseError;
+
+// Row-major matrix stored in a single contiguous `Vec`, cell (row, col) being at `row * cols + col`.
+// Cells are addressed by anything convertible into a `(row, col)` tuple, such as a `grid::Pos`.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mat2D<T> {
+  data: Vec<T>,
+  rows: usize,
+  cols: usize,
+}
+
+/// Rows must all have the same length.
+impl<T, II> FromIterator<II> for Mat2D<T> where II: IntoIterator<Item=T> {
+  /// # Panics
+  ///
+  /// If a row is longer or shorter than the first one.
+  /// Use [`Mat2D::try_from_lines`] to get a `ParseError` instead when the rows come from untrusted text.
+  fn from_iter<III>( iter: III ) -> Self where III: IntoIterator<Item=II> {
+    let mut data = Vec::new();
+    let mut rows = 0;
+    let mut cols = None;
+    for line in iter {
+      let len = data.len();
+      data.extend( line );
+      let row_len = data.len() - len;
+      assert!( cols.is_none_or( |cols| cols == row_len ), "rows should all have the same length" );
+      cols = Some( row_len );
+      rows += 1;
+    }
+    Self { data, rows, cols: cols.unwrap_or_default() }
+  }
+}
+
+impl<T: Clone> Mat2D<T> {
+  pub fn new( rows: usize, cols: usize, fill: T ) -> Self {
+    Self { data: vec!( fill; rows * cols ), rows, cols }
+  }
+
+  pub fn fill( &mut self, value: T ) {
+    self.data.fill( value );
+  }
+}
+
+impl<T> Mat2D<T> {
+  /// Row-major `data`, `None` if its length isn't `rows * cols`.
+  pub fn from_vec( rows: usize, cols: usize, data: Vec<T> ) -> Option<Self> {
+    (data.len() == rows * cols).then_some( Self { data, rows, cols } )
+  }
+
+  /// One cell per char of each line of `input`, `cell` rejecting chars with `expected`.
+  /// Every line must have as many cells as the first one.
+  pub fn try_from_lines( input: &str, mut cell: impl FnMut( char ) -> Option<T>, expected: &str ) -> Result<Self, ParseError> {
+    let mut data = Vec::with_capacity( input.len() );
+    let mut rows = 0;
+    let mut cols = None;
+    for line in input.lines() {
+      let row_start = data.len();
+      for (idx, char) in line.char_indices() {
+        let row_len = data.len() - row_start;
+        if cols == Some(row_len) {
+          return Err( ParseError::new( input, &line[idx..], format!( "{row_len} cells per line" ) ) );
+        }
+        let value = cell( char )
+          .ok_or_else( || ParseError::new( input, &line[idx..idx+char.len_utf8()], expected ) )?;
+        data.push( value );
+      }
+      let row_len = data.len() - row_start;
+      match cols {
+        Some(cols) if row_len < cols =>
+          return Err( ParseError::new( input, &line[line.len()..], format!( "{cols} cells per line" ) ) ),
+        _ => cols = Some( row_len ),
+      }
+      rows += 1;
+    }
+    Ok( Self { data, rows, cols: cols.unwrap_or_default() } )
+  }
+
+  pub fn rows( &self ) -> usize {
+    self.rows
+  }
+
+  pub fn cols( &self ) -> usize {
+    self.cols
+  }
+
+  fn index_of( &self, position: impl Into<(usize, usize)> ) -> Option<usize> {
+    let (row, col) = position.into();
+    (row < self.rows && col < self.cols).then_some( row * self.cols + col )
+  }
+
+  pub fn contains( &self, position: impl Into<(usize, usize)> ) -> bool {
+    self.index_of( position ).is_some()
+  }
+
+  pub fn get( &self, position: impl Into<(usize, usize)> ) -> Option<&T> {
+    self.index_of( position )
+      .map( |idx| &self.data[idx] )
+  }
+
+  pub fn iter( &self ) -> impl Iterator<Item=((usize,usize), &T)> {
+    let cols = self.cols;
+    self.data.iter().enumerate()
+      .map( move |(idx, value)| ((idx / cols, idx % cols), value) )
+  }
+
+  /// Every cell, row after row.
+  pub fn as_slice( &self ) -> &[T] {
+    &self.data
+  }
+
+  pub fn row( &self, row: usize ) -> Option<&[T]> {
+    (row < self.rows).then( || &self.data[ row*self.cols..(row+1)*self.cols ] )
+  }
+
+  pub fn iter_rows( &self ) -> impl Iterator<Item=&[T]> {
+    (0..self.rows).map( |row| &self.data[ row*self.cols..(row+1)*self.cols ] )
+  }
+
+  /// Cells of column `col` from top to bottom, empty if `col` is out of bounds.
+  pub fn col( &self, col: usize ) -> impl Iterator<Item=&T> {
+    let (start, rows) = if col < self.cols { (col, self.rows) } else { (0, 0) };
+    self.data.iter()
+      .skip( start )
+      .step_by( self.cols.max(1) )
+      .take( rows )
+  }
+
+  pub fn iter_cols( &self ) -> impl Iterator<Item=impl Iterator<Item=&T>> {
+    (0..self.cols).map( |col| self.col(col) )
+  }
+
+  pub fn map<U>( &self, f: impl FnMut( &T ) -> U ) -> Mat2D<U> {
+    Mat2D {
+      data: self.data.iter().map( f ).collect(),
+      rows: self.rows,
+      cols: self.cols,
+    }
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+impl<T> Mat2D<T> {
+  pub fn get_mut( &mut self, position: impl Into<(usize, usize)> ) -> Option<&mut T> {
+    self.index_of( position )
+      .map( |idx| &mut self.data[idx] )
+  }
+
+  /// Replaces the cell at `position`, returning its previous value, `None` if out of bounds.
+  pub fn set( &mut self, position: impl Into<(usize, usize)>, value: T ) -> Option<T> {
+    self.get_mut( position )
+      .map( |cell| mem::replace( cell, value ) )
+  }
+
+  /// Panics if either position is out of bounds.
+  pub fn swap( &mut self, one: impl Into<(usize, usize)>, other: impl Into<(usize, usize)> ) {
+    let (one, other) = (self.checked_index( one ), self.checked_index( other ));
+    self.data.swap( one, other );
+  }
+
+  pub fn iter_mut( &mut self ) -> impl Iterator<Item=((usize,usize), &mut T)> {
+    let cols = self.cols;
+    self.data.iter_mut().enumerate()
+      .map( move |(idx, value)| ((idx / cols, idx % cols), value) )
+  }
+
+  pub fn as_mut_slice( &mut self ) -> &mut [T] {
+    &mut self.data
+  }
+
+  pub fn row_mut( &mut self, row: usize ) -> Option<&mut [T]> {
+    (row < self.rows).then( || &mut self.data[ row*self.cols..(row+1)*self.cols ] )
+  }
+
+  /// Mirrors left to right, reversing every row.
+  pub fn flip_horizontal( &mut self ) {
+    if self.cols > 0 {
+      self.data.chunks_exact_mut( self.cols ).for_each( <[T]>::reverse );
+    }
+  }
+
+  /// Mirrors top to bottom, reversing the order of the rows.
+  pub fn flip_vertical( &mut self ) {
+    for row in 0..self.rows/2 {
+      let other = self.rows - 1 - row;
+      let (top, bottom) = self.data.split_at_mut( other * self.cols );
+      top[ row*self.cols..(row+1)*self.cols ].swap_with_slice( &mut bottom[ ..self.cols ] );
+    }
+  }
+
+  /// Transposes in place by following the permutation cycles, a `rows x cols` matrix becoming `cols x rows`.
+  pub fn transpose( &mut self ) {
+    let (rows, cols) = (self.rows, self.cols);
+    let destination = |idx: usize| (idx % cols) * rows + idx / cols;
+    let mut visited = vec!( false; self.data.len() );
+    for start in 0..self.data.len() {
+      if visited[start] { continue; }
+      visited[start] = true;
+      let mut idx = destination( start );
+      while idx != start {
+        self.data.swap( start, idx );
+        visited[idx] = true;
+        idx = destination( idx );
+      }
+    }
+    (self.rows, self.cols) = (cols, rows);
+  }
+
+  /// Quarter turn clockwise.
+  pub fn rotate_right( &mut self ) {
+    self.transpose();
+    self.flip_horizontal();
+  }
+
+  /// Quarter turn counter-clockwise.
+  pub fn rotate_left( &mut self ) {
+    self.transpose();
+    self.flip_vertical();
+  }
+
+  fn checked_index( &self, position: impl Into<(usize, usize)> ) -> usize {
+    let position = position.into();
+    self.index_of( position )
+      .unwrap_or_else( || panic!( "position {position:?} should be within {}x{}", self.rows, self.cols ) )
+  }
+}
+
+impl<T, P> ops::Index<P> for Mat2D<T> where P: Into<(usize, usize)> {
+  type Output = T;
+
+  fn index( &self, position: P ) -> &Self::Output {
+    &self.data[ self.checked_index(position) ]
+  }
+}
+
+impl<T, P> ops::IndexMut<P> for Mat2D<T> where P: Into<(usize, usize)> {
+  fn index_mut( &mut self, position: P ) -> &mut Self::Output {
+    let idx = self.checked_index( position );
+    &mut self.data[idx]
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+
+  #[test]
+  fn test_mat2d() {
+    let data =
+     "aaaaaaaa
+bbbbbbbb
+cccccccc
+dddddddd
+eeeeeeee
+ffffffff
+gggggggg
+hhhhhhhh";
+
+    let mat = data.lines()
+      .map( str::chars )
+      .collect::<Mat2D<_>>();
+
+    assert_eq!( Some(&'a'), mat.get((0,0)) );
+    assert_eq!( Some(&'a'), mat.get((0,7)) );
+    assert_eq!( Some(&'h'), mat.get((7,0)) );
+    assert_eq!( Some(&'h'), mat.get((7,7)) );
+    assert_eq!( None, mat.get((0,8)) );
+    assert_eq!( (8, 8), (mat.rows(), mat.cols()) );
+  }
+
+  #[test]
+  fn test_mat2d_constructors() {
+    let mat = Mat2D::new( 2, 3, 0 );
+    assert_eq!( (2, 3), (mat.rows(), mat.cols()) );
+    assert_eq!( Some(&0), mat.get((1,2)) );
+    assert_eq!( None, mat.get((2,0)) );
+
+    let mat = Mat2D::from_vec( 2, 3, vec![1, 2, 3, 4, 5, 6] ).unwrap();
+    assert_eq!( Some(&4), mat.get((1,0)) );
+    assert_eq!( [[1, 2, 3], [4, 5, 6]].into_iter().collect::<Mat2D<_>>(), mat );
+    assert!( Mat2D::from_vec( 2, 3, vec![1, 2, 3] ).is_none() );
+
+    let empty = Mat2D::<u8>::from_vec( 0, 0, Vec::new() ).unwrap();
+    assert_eq!( (0, 0), (empty.rows(), empty.cols()) );
+  }
+
+  #[test]
+  fn test_mat2d_try_from_lines() {
+    let digit = |char: char| char.to_digit( 10 );
+    let mat = Mat2D::try_from_lines( "123\n456", digit, "a digit" ).unwrap();
+    assert_eq!( (2, 3), (mat.rows(), mat.cols()) );
+    assert_eq!( Some(&6), mat.get((1,2)) );
+
+    let error = Mat2D::try_from_lines( "123\n4x6", digit, "a digit" ).unwrap_err();
+    assert_eq!( (2, 2, "x"), (error.line, error.column, error.text.as_str()) );
+    let error = Mat2D::try_from_lines( "123\n4567", digit, "a digit" ).unwrap_err();
+    assert_eq!( (2, 4, "7"), (error.line, error.column, error.text.as_str()) );
+    let error = Mat2D::try_from_lines( "123\n45\n678", digit, "a digit" ).unwrap_err();
+    assert_eq!( (2, 3, ""), (error.line, error.column, error.text.as_str()) );
+    assert_eq!( "3 cells per line", error.expected );
+  }
+
+  #[test]
+  #[should_panic( expected = "rows should all have the same length" )]
+  fn test_mat2d_ragged() {
+    let _ = ["abc", "ab"].into_iter().map( str::chars ).collect::<Mat2D<_>>();
+  }
+
+  #[test]
+  #[should_panic( expected = "rows should all have the same length" )]
+  fn test_mat2d_ragged_longer() {
+    let _ = ["ab", "abc"].into_iter().map( str::chars ).collect::<Mat2D<_>>();
+  }
+
+  #[test]
+  fn test_mat2d_ragged_try_from_lines() {
+    let error = Mat2D::try_from_lines( "abc\nab", Some, "a letter" ).unwrap_err();
+    assert_eq!( (2, 3), (error.line, error.column) );
+    assert_eq!( "3 cells per line", error.expected );
+  }
+
+  #[test]
+  fn test_mat2d_slices() {
+    let mat = Mat2D::from_vec( 2, 3, vec![1, 2, 3, 4, 5, 6] ).unwrap();
+    assert_eq!( &[1, 2, 3, 4, 5, 6], mat.as_slice() );
+    assert_eq!( Some(&[4, 5, 6][..]), mat.row(1) );
+    assert_eq!( None, mat.row(2) );
+    assert_eq!( vec![&[1, 2, 3][..], &[4, 5, 6][..]], mat.iter_rows().collect::<Vec<_>>() );
+    assert_eq!( vec![&3, &6], mat.col(2).collect::<Vec<_>>() );
+    assert_eq!( 0, mat.col(3).count() );
+    assert_eq!( vec![vec![1, 4], vec![2, 5], vec![3, 6]], mat.iter_cols().map( |col| col.copied().collect::<Vec<_>>() ).collect::<Vec<_>>() );
+    assert_eq!( vec![((0,0), &1), ((0,1), &2), ((1,2), &6)], mat.iter().filter( |(_, value)| **value != 3 && **value < 4 || **value == 6 ).collect::<Vec<_>>() );
+  }
+
+  #[test]
+  fn test_mat2d_mutation() {
+    let mut mat = Mat2D::new( 2, 3, '.' );
+    mat[(1,2)] = '#';
+    assert_eq!( '#', mat[(1,2)] );
+    assert_eq!( Some('.'), mat.set( (0,0), '^' ) );
+    assert_eq!( None, mat.set( (2,0), '^' ) );
+    *mat.get_mut( (0,1) ).unwrap() = 'x';
+    assert_eq!( None, mat.get_mut((0,3)) );
+
+    mat.swap( (0,0), (1,2) );
+    assert_eq!( "#x.\n..^", to_string(&mat) );
+
+    mat.iter_mut()
+      .filter( |((row, _), _)| *row == 1 )
+      .for_each( |(_, cell)| *cell = 'o' );
+    assert_eq!( "#x.\nooo", to_string(&mat) );
+
+    let codes = mat.map( |cell| *cell as u32 );
+    assert_eq!( Some(&('o' as u32)), codes.get((1,0)) );
+
+    mat.fill( '.' );
+    assert!( mat.as_slice().iter().all( |cell| *cell == '.' ) );
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_mat2d_index_out_of_bounds() {
+    let mat = Mat2D::new( 2, 3, '.' );
+    let _ = mat[(0,3)];
+  }
+
+  #[test]
+  fn test_mat2d_transforms() {
+    let mat = Mat2D::try_from_lines( "abc\ndef", Some, "a letter" ).unwrap();
+
+    let mut actual = mat.clone();
+    actual.transpose();
+    assert_eq!( (3, 2), (actual.rows(), actual.cols()) );
+    assert_eq!( "ad\nbe\ncf", to_string(&actual) );
+    actual.transpose();
+    assert_eq!( mat, actual );
+
+    actual.rotate_right();
+    assert_eq!( "da\neb\nfc", to_string(&actual) );
+    actual.rotate_left();
+    assert_eq!( mat, actual );
+
+    actual.flip_horizontal();
+    assert_eq!( "cba\nfed", to_string(&actual) );
+    actual.flip_vertical();
+    assert_eq!( "fed\ncba", to_string(&actual) );
+
+    let mut square = Mat2D::from_vec( 3, 3, (0..9).collect() ).unwrap();
+    square.rotate_left();
+    assert_eq!( &[2, 5, 8, 1, 4, 7, 0, 3, 6], square.as_slice() );
+  }
+
+  fn to_string( mat: &Mat2D<char> ) -> String {
+    mat.iter_rows()
+      .map( |row| row.iter().collect::<String>() )
+      .collect::<Vec<_>>()
+      .join( "\n" )
+  }
+}
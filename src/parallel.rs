@@ -0,0 +1,49 @@
+// Loops over independent items go through `maybe_par_iter()` : a rayon parallel iterator with the `parallel` feature,
+// a plain iterator otherwise. Both expose the same adapters (filter, map, count, sum, collect...), so the same code
+// compiles either way, and collecting keeps the items order in both cases.
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "parallel")]
+pub use rayon::prelude::*;
+
+pub trait MaybeParallel<'a> {
+  type Iter;
+
+  fn maybe_par_iter( &'a self ) -> Self::Iter;
+}
+
+#[cfg(feature = "parallel")]
+impl<'a, T: Sync + 'a> MaybeParallel<'a> for [T] {
+  type Iter = rayon::slice::Iter<'a, T>;
+
+  fn maybe_par_iter( &'a self ) -> Self::Iter {
+    self.par_iter()
+  }
+}
+
+#[cfg(not(feature = "parallel"))]
+impl<'a, T: 'a> MaybeParallel<'a> for [T] {
+  type Iter = std::slice::Iter<'a, T>;
+
+  fn maybe_par_iter( &'a self ) -> Self::Iter {
+    self.iter()
+  }
+}
+
+// =================================================================================================================================
+
+// without the feature, both sides would be the same serial iterator
+#[cfg(all(test, feature = "parallel"))]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_same_as_serial() {
+    let values = (0..10_000_u64).collect::<Vec<_>>();
+    let serial = values.iter().map( |value| value * value ).filter( |value| value % 3 == 1 ).collect::<Vec<_>>();
+    let maybe_parallel = values.maybe_par_iter().map( |value| value * value ).filter( |value| value % 3 == 1 ).collect::<Vec<_>>();
+    assert_eq!( serial, maybe_parallel );
+    assert_eq!( serial.iter().sum::<u64>(), values.maybe_par_iter().map( |value| value * value ).filter( |value| value % 3 == 1 ).sum::<u64>() );
+  }
+}
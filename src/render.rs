@@ -0,0 +1,282 @@
+use std::{fmt, io, iter, ops};
+use crate::{grid::Pos, Mat2D};
+
+// Drawing grids for debugging : a Canvas is built from any Mat2D whose cells display as a single char, or from lines of
+// text, overlays then recolor or replace some cells, and the result is shown as ANSI text or written as a binary PPM/PGM
+// image.
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+  Red,
+  Green,
+  Yellow,
+  Blue,
+  Magenta,
+  Cyan,
+  White,
+  Grey,
+}
+
+impl Color {
+  /// Foreground SGR code.
+  fn ansi( self ) -> u8 {
+    match self {
+      Color::Red => 31,
+      Color::Green => 32,
+      Color::Yellow => 33,
+      Color::Blue => 34,
+      Color::Magenta => 35,
+      Color::Cyan => 36,
+      Color::White => 97,
+      Color::Grey => 90,
+    }
+  }
+
+  pub fn rgb( self ) -> [u8; 3] {
+    match self {
+      Color::Red => [220, 50, 47],
+      Color::Green => [64, 192, 64],
+      Color::Yellow => [230, 200, 40],
+      Color::Blue => [38, 110, 230],
+      Color::Magenta => [200, 60, 200],
+      Color::Cyan => [42, 190, 190],
+      Color::White => [255, 255, 255],
+      Color::Grey => [128, 128, 128],
+    }
+  }
+}
+
+/// Grey level of an RGB color, ITU-R BT.601 weights.
+fn luma( [r, g, b]: [u8; 3] ) -> u8 {
+  ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Cells to highlight on a [`Canvas`], in a given color and optionally with a replacement glyph.
+#[derive(Debug, Clone)]
+pub struct Overlay {
+  cells: Vec<Pos>,
+  color: Color,
+  glyph: Option<char>,
+}
+
+impl Overlay {
+  pub fn new( cells: impl IntoIterator<Item=impl Into<Pos>>, color: Color ) -> Self {
+    Self { cells: cells.into_iter().map( Into::into ).collect(), color, glyph: None }
+  }
+
+  /// Cells explored by a search or a simulation, keeping their glyph.
+  pub fn visited( cells: impl IntoIterator<Item=impl Into<Pos>> ) -> Self {
+    Self::new( cells, Color::Blue )
+  }
+
+  /// Cells along a path, keeping their glyph.
+  pub fn path( cells: impl IntoIterator<Item=impl Into<Pos>> ) -> Self {
+    Self::new( cells, Color::Yellow )
+  }
+
+  /// Columns `cols` of row `row`, such as a word of a [`Canvas::text`].
+  pub fn span( row: usize, cols: ops::Range<usize>, color: Color ) -> Self {
+    Self::new( cols.map( |col| (row, col) ), color )
+  }
+
+  /// A single point of interest drawn as `glyph`.
+  pub fn marker( cell: impl Into<Pos>, glyph: char ) -> Self {
+    Self::new( [cell], Color::Red ).with_glyph( glyph )
+  }
+
+  pub fn with_color( self, color: Color ) -> Self {
+    Self { color, ..self }
+  }
+
+  pub fn with_glyph( self, glyph: char ) -> Self {
+    Self { glyph: Some(glyph), ..self }
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pixel {
+  glyph: char,
+  color: Option<Color>,
+}
+
+/// Rendered grid, one glyph and an optional color per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+  pixels: Mat2D<Pixel>,
+}
+
+impl Canvas {
+  /// Uncolored canvas, each cell drawn as the first char of its `Display` output.
+  pub fn new<T: fmt::Display>( mat: &Mat2D<T> ) -> Self {
+    Self::with_palette( mat, |_| None )
+  }
+
+  /// Uncolored canvas of `text`, a row per line, lines shorter than the longest one padded with blanks.
+  pub fn text( text: &str ) -> Self {
+    let cols = text.lines().map( |line| line.chars().count() ).max().unwrap_or( 0 );
+    let mat = text.lines()
+      .map( |line| line.chars().chain( iter::repeat( ' ' ) ).take( cols ) )
+      .collect::<Mat2D<char>>();
+    Self::new( &mat )
+  }
+
+  /// Canvas where `palette` picks the base color of each cell.
+  pub fn with_palette<T: fmt::Display>( mat: &Mat2D<T>, palette: impl Fn( &T ) -> Option<Color> ) -> Self {
+    let pixels = mat.map( |cell| Pixel {
+      glyph: cell.to_string().chars().next().unwrap_or( ' ' ),
+      color: palette( cell ),
+    });
+    Self { pixels }
+  }
+
+  /// Applies `overlay` on top of what is already drawn, cells outside of the canvas being ignored.
+  pub fn overlay( mut self, overlay: &Overlay ) -> Self {
+    for cell in &overlay.cells {
+      if let Some(pixel) = self.pixels.get_mut( *cell ) {
+        pixel.color = Some( overlay.color );
+        pixel.glyph = overlay.glyph.unwrap_or( pixel.glyph );
+      }
+    }
+    self
+  }
+
+  pub fn rows( &self ) -> usize {
+    self.pixels.rows()
+  }
+
+  pub fn cols( &self ) -> usize {
+    self.pixels.cols()
+  }
+
+  /// Colored rendering, for terminals.
+  pub fn ansi( &self ) -> Ansi<'_> {
+    Ansi( self )
+  }
+
+  /// Binary RGB image, each cell drawn as a `scale`x`scale` square.
+  /// Uncolored cells are black when blank or '.', grey otherwise.
+  pub fn write_ppm( &self, writer: &mut impl io::Write, scale: usize ) -> io::Result<()> {
+    self.write_image( writer, "P6", scale, |rgb| rgb.to_vec() )
+  }
+
+  /// Binary greyscale image, see [`Canvas::write_ppm`].
+  pub fn write_pgm( &self, writer: &mut impl io::Write, scale: usize ) -> io::Result<()> {
+    self.write_image( writer, "P5", scale, |rgb| vec!( luma(rgb) ) )
+  }
+
+  fn write_image( &self, writer: &mut impl io::Write, magic: &str, scale: usize, encode: impl Fn( [u8; 3] ) -> Vec<u8> )
+    -> io::Result<()> {
+    let scale = scale.max( 1 );
+    write!( writer, "{magic}\n{} {}\n255\n", self.cols() * scale, self.rows() * scale )?;
+    for row in self.pixels.iter_rows() {
+      let line = row.iter()
+        .flat_map( |pixel| encode( pixel.rgb() ).repeat( scale ) )
+        .collect::<Vec<_>>();
+      for _ in 0..scale {
+        writer.write_all( &line )?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl Pixel {
+  fn rgb( &self ) -> [u8; 3] {
+    match (self.color, self.glyph) {
+      (Some(color), _) => color.rgb(),
+      (None, ' ' | '.') => [0, 0, 0],
+      (None, _) => [192, 192, 192],
+    }
+  }
+}
+
+/// Plain text, without colors.
+impl fmt::Display for Canvas {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    for row in self.pixels.iter_rows() {
+      let line = row.iter().map( |pixel| pixel.glyph ).collect::<String>();
+      writeln!( f, "{line}" )?;
+    }
+    Ok(())
+  }
+}
+
+/// ANSI colored text, see [`Canvas::ansi`].
+pub struct Ansi<'a>( pub &'a Canvas );
+
+impl fmt::Display for Ansi<'_> {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    for row in self.0.pixels.iter_rows() {
+      let mut current = None;
+      for pixel in row {
+        if pixel.color != current {
+          match pixel.color {
+            Some(color) => write!( f, "\x1b[{}m", color.ansi() )?,
+            None => write!( f, "\x1b[0m" )?,
+          }
+          current = pixel.color;
+        }
+        write!( f, "{}", pixel.glyph )?;
+      }
+      if current.is_some() {
+        write!( f, "\x1b[0m" )?;
+      }
+      writeln!( f )?;
+    }
+    Ok(())
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn canvas() -> Canvas {
+    let mat = Mat2D::try_from_lines( "#..\n.1.", Some, "a cell" ).unwrap();
+    Canvas::new( &mat )
+      .overlay( &Overlay::path( [(1, 0), (1, 1)] ) )
+      .overlay( &Overlay::marker( (1, 1), '@' ) )
+      .overlay( &Overlay::visited( [(5, 5)] ) )
+  }
+
+  #[test]
+  fn test_text() {
+    assert_eq!( "#..\n.@.\n", canvas().to_string() );
+    assert_eq!( "#..\n\x1b[33m.\x1b[31m@\x1b[0m.\n", canvas().ansi().to_string() );
+  }
+
+  #[test]
+  fn test_text_canvas() {
+    let canvas = Canvas::text( "12 34\n5\n" ).overlay( &Overlay::span( 0, 3..5, Color::Green ) );
+    assert_eq!( (2, 5), (canvas.rows(), canvas.cols()) );
+    assert_eq!( "12 34\n5    \n", canvas.to_string() );
+    assert_eq!( "12 \x1b[32m34\x1b[0m\n5    \n", canvas.ansi().to_string() );
+  }
+
+  #[test]
+  fn test_images() {
+    let mut ppm = Vec::new();
+    canvas().write_ppm( &mut ppm, 1 ).unwrap();
+    let (header, data) = ppm.split_at( b"P6\n3 2\n255\n".len() );
+    assert_eq!( b"P6\n3 2\n255\n", header );
+    assert_eq!( 3 * 6, data.len() );
+    assert_eq!( [192, 192, 192, 0, 0, 0], data[..6] );
+    assert_eq!( Color::Yellow.rgb(), data[9..12] );
+
+    let mut pgm = Vec::new();
+    canvas().write_pgm( &mut pgm, 2 ).unwrap();
+    let (header, data) = pgm.split_at( b"P5\n6 4\n255\n".len() );
+    assert_eq!( b"P5\n6 4\n255\n", header );
+    assert_eq!( 6 * 4, data.len() );
+    assert_eq!( [192, 192, 0, 0, 0, 0], data[..6] );
+    assert_eq!( data[..6], data[6..12] );
+  }
+}
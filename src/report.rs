@@ -0,0 +1,50 @@
+use std::fmt;
+
+// Detailed results of a part for analysis, beyond its answer : shown as text by `aoc report`, or saved as JSON.
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Human readable through `Display`, machine readable through `json`.
+pub trait Report: fmt::Display {
+  fn json( &self ) -> String;
+}
+
+pub type BoxedReport = Box<dyn Report>;
+
+/// `text` as a quoted JSON string.
+pub fn json_string( text: &str ) -> String {
+  let mut escaped = String::with_capacity( text.len() + 2 );
+  escaped.push( '"' );
+  for char in text.chars() {
+    match char {
+      '"' => escaped.push_str( "\\\"" ),
+      '\\' => escaped.push_str( "\\\\" ),
+      '\n' => escaped.push_str( "\\n" ),
+      '\r' => escaped.push_str( "\\r" ),
+      '\t' => escaped.push_str( "\\t" ),
+      char if (char as u32) < 0x20 => escaped.push_str( &format!( "\\u{:04x}", char as u32 ) ),
+      char => escaped.push( char ),
+    }
+  }
+  escaped.push( '"' );
+  escaped
+}
+
+/// Comma separated JSON array of the already rendered `items`.
+pub fn json_array( items: impl IntoIterator<Item=String> ) -> String {
+  format!( "[{}]", items.into_iter().collect::<Vec<_>>().join( ", " ) )
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_json() {
+    assert_eq!( r#""a\"b\\c\nd\u001b""#, json_string( "a\"b\\c\nd\x1b" ) );
+    assert_eq!( "[1, \"x\"]", json_array( ["1".to_owned(), json_string("x")] ) );
+    assert_eq!( "[]", json_array( [] ) );
+  }
+}
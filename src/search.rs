@@ -0,0 +1,258 @@
+use std::{cmp::Reverse, collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque}, hash::Hash};
+use crate::{grid::{Direction, Pos}, Mat2D};
+
+// Graph searches over anything able to list the weighted successors of a node, explicit or implicit.
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+pub trait Graph {
+  type Node: Copy + Eq + Hash + Ord;
+
+  /// Successors of `node` with the cost of the edge leading to each of them.
+  fn neighbours( &self, node: Self::Node ) -> impl Iterator<Item=(Self::Node, usize)>;
+}
+
+/// Adjacency map, every edge costing 1.
+impl<N> Graph for HashMap<N, Vec<N>> where N: Copy + Eq + Hash + Ord {
+  type Node = N;
+
+  fn neighbours( &self, node: N ) -> impl Iterator<Item=(N, usize)> {
+    self.get( &node ).into_iter()
+      .flatten()
+      .map( |next| (*next, 1) )
+  }
+}
+
+/// A grid cell as seen by the [`GridGraph`] closures : its position and its value.
+pub type Cell<'mat, T> = (Pos, &'mat T);
+
+/// Implicit graph over the cells of a [`Mat2D`], moving orthogonally. `cost` returns the cost of moving
+/// from one cell to a neighbouring one, `None` when that move isn't allowed.
+pub struct GridGraph<'mat, T, F> {
+  mat: &'mat Mat2D<T>,
+  cost: F,
+}
+
+impl<'mat, T, F> GridGraph<'mat, T, F> where F: Fn( Cell<'_, T>, Cell<'_, T> ) -> Option<usize> {
+  pub fn new( mat: &'mat Mat2D<T>, cost: F ) -> Self {
+    Self { mat, cost }
+  }
+}
+
+impl<'mat, T> GridGraph<'mat, T, ()> {
+  /// Unit cost graph, `passable` telling whether a move from one cell to a neighbouring one is allowed.
+  pub fn passable( mat: &'mat Mat2D<T>, passable: impl Fn( Cell<'_, T>, Cell<'_, T> ) -> bool )
+    -> GridGraph<'mat, T, impl Fn( Cell<'_, T>, Cell<'_, T> ) -> Option<usize>> {
+    GridGraph::new( mat, move |from, to| passable( from, to ).then_some( 1 ) )
+  }
+}
+
+impl<T, F> Graph for GridGraph<'_, T, F> where F: Fn( Cell<'_, T>, Cell<'_, T> ) -> Option<usize> {
+  type Node = Pos;
+
+  fn neighbours( &self, node: Pos ) -> impl Iterator<Item=(Pos, usize)> {
+    let from = (node, &self.mat[node]);
+    Direction::DIRECTIONS.into_iter()
+      .filter_map( move |direction| node.step( direction, self.mat ) )
+      .filter_map( move |next| (self.cost)( from, (next, &self.mat[next]) ).map( |cost| (next, cost) ) )
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Outcome of a search : every reached node with its distance from the closest start,
+/// the node it was reached from, and the order in which nodes were settled.
+#[derive(Debug, Clone)]
+pub struct Search<N> {
+  pub distances: HashMap<N, usize>,
+  pub predecessors: HashMap<N, N>,
+  pub order: Vec<N>,
+}
+
+impl<N> Search<N> where N: Copy + Eq + Hash {
+  fn new() -> Self {
+    Self { distances: HashMap::new(), predecessors: HashMap::new(), order: Vec::new() }
+  }
+
+  pub fn distance( &self, node: N ) -> Option<usize> {
+    self.distances.get( &node ).copied()
+  }
+
+  pub fn is_reached( &self, node: N ) -> bool {
+    self.distances.contains_key( &node )
+  }
+
+  /// Nodes from a start to `target`, both included, following the predecessors.
+  pub fn path_to( &self, target: N ) -> Option<Vec<N>> {
+    self.distances.get( &target )?;
+    let mut path = vec!( target );
+    while let Some(previous) = self.predecessors.get( path.last().unwrap() ) {
+      path.push( *previous );
+    }
+    path.reverse();
+    Some( path )
+  }
+}
+
+/// Breadth-first search, distances counting edges whatever their cost.
+pub fn bfs<G: Graph>( graph: &G, starts: impl IntoIterator<Item=G::Node> ) -> Search<G::Node> {
+  let mut search = Search::new();
+  let mut queue = VecDeque::new();
+  for start in starts {
+    if search.distances.insert( start, 0 ).is_none() {
+      queue.push_back( start );
+    }
+  }
+
+  while let Some(node) = queue.pop_front() {
+    search.order.push( node );
+    let distance = search.distances[ &node ] + 1;
+    for (next, _) in graph.neighbours( node ) {
+      if let Entry::Vacant(entry) = search.distances.entry( next ) {
+        entry.insert( distance );
+        search.predecessors.insert( next, node );
+        queue.push_back( next );
+      }
+    }
+  }
+  search
+}
+
+/// Depth-first search, distances being depths in the resulting DFS tree.
+pub fn dfs<G: Graph>( graph: &G, start: G::Node ) -> Search<G::Node> {
+  let mut search = Search::new();
+  let mut stack = vec!( (start, None, 0) );
+  while let Some((node, predecessor, depth)) = stack.pop() {
+    if search.distances.contains_key( &node ) {
+      continue;
+    }
+    search.distances.insert( node, depth );
+    if let Some(predecessor) = predecessor {
+      search.predecessors.insert( node, predecessor );
+    }
+    search.order.push( node );
+
+    let neighbours = graph.neighbours( node ).collect::<Vec<_>>();
+    for (next, _) in neighbours.into_iter().rev() {
+      if !search.distances.contains_key( &next ) {
+        stack.push( (next, Some(node), depth+1) );
+      }
+    }
+  }
+  search
+}
+
+/// Cheapest distances from the closest of `starts` to every reachable node.
+pub fn dijkstra<G: Graph>( graph: &G, starts: impl IntoIterator<Item=G::Node> ) -> Search<G::Node> {
+  let (search, _) = best_first( graph, starts, |_| 0, |_| false );
+  search
+}
+
+/// Cheapest path from `start` to the first node satisfying `is_goal`. Returns the total cost and the path,
+/// both ends included.
+///
+/// `heuristic` estimates the remaining cost and must be consistent : never more than the cost of an edge
+/// plus its estimate from the other end, and 0 on goals. Settled nodes are never reopened, so an admissible
+/// but inconsistent heuristic can return a path that isn't the cheapest.
+pub fn astar<G: Graph>( graph: &G,
+                        start: G::Node,
+                        is_goal: impl Fn( G::Node ) -> bool,
+                        heuristic: impl Fn( G::Node ) -> usize ) -> Option<(usize, Vec<G::Node>)> {
+  let (search, goal) = best_first( graph, [start], heuristic, is_goal );
+  let goal = goal?;
+  Some( (search.distance( goal )?, search.path_to( goal )?) )
+}
+
+fn best_first<G: Graph>( graph: &G,
+                         starts: impl IntoIterator<Item=G::Node>,
+                         heuristic: impl Fn( G::Node ) -> usize,
+                         is_goal: impl Fn( G::Node ) -> bool ) -> (Search<G::Node>, Option<G::Node>) {
+  let mut search = Search::new();
+  let mut settled = HashSet::new();
+  let mut heap = BinaryHeap::new();
+  for start in starts {
+    search.distances.insert( start, 0 );
+    heap.push( Reverse((heuristic(start), 0, start)) );
+  }
+
+  while let Some(Reverse((_, distance, node))) = heap.pop() {
+    if !settled.insert( node ) || distance > search.distances[ &node ] {
+      continue;
+    }
+    search.order.push( node );
+    if is_goal( node ) {
+      return (search, Some(node));
+    }
+    for (next, cost) in graph.neighbours( node ) {
+      let next_distance = distance + cost;
+      if search.distances.get( &next ).is_none_or( |known| next_distance < *known ) {
+        search.distances.insert( next, next_distance );
+        search.predecessors.insert( next, node );
+        heap.push( Reverse((next_distance + heuristic(next), next_distance, next)) );
+      }
+    }
+  }
+  (search, None)
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const TEST_MAZE: &str = "S.#.....
+.##.###.
+....#...
+.##...#E";
+
+  fn maze() -> (Mat2D<char>, Pos, Pos) {
+    let mat = Mat2D::try_from_lines( TEST_MAZE, Some, "a maze cell" ).unwrap();
+    let find = |target: char| mat.iter()
+      .find_map( |(position, cell)| (*cell == target).then_some( Pos::from(position) ) )
+      .unwrap();
+    let (start, end) = (find( 'S' ), find( 'E' ));
+    (mat, start, end)
+  }
+
+  #[test]
+  fn test_bfs() {
+    let (mat, start, end) = maze();
+    let graph = GridGraph::passable( &mat, |_, (_, cell)| *cell != '#' );
+    let search = bfs( &graph, [start] );
+    assert_eq!( Some(12), search.distance(end) );
+    let path = search.path_to( end ).unwrap();
+    assert_eq!( 13, path.len() );
+    assert_eq!( (start, end), (path[0], path[12]) );
+    assert!( path.windows(2).all( |step| (step[1] - step[0]).row.abs() + (step[1] - step[0]).col.abs() == 1 ) );
+    assert!( !search.is_reached( Pos::new(0, 2) ) );
+  }
+
+  #[test]
+  fn test_dfs() {
+    let graph = HashMap::from( [(1, vec![2, 3]), (2, vec![4]), (3, vec![4]), (4, vec![])] );
+    let search = dfs( &graph, 1 );
+    assert_eq!( vec![1, 2, 4, 3], search.order );
+    assert_eq!( Some(vec![1, 2, 4]), search.path_to(4) );
+    assert_eq!( None, search.path_to(5) );
+  }
+
+  #[test]
+  fn test_dijkstra_and_astar() {
+    let (mat, start, end) = maze();
+    // walking into a '.' costs 1, squeezing through a '#' costs 2 : cutting through (0,2) beats the 12 steps detour
+    let graph = GridGraph::new( &mat, |_, (_, cell): Cell<char>| Some( if *cell == '#' { 2 } else { 1 } ) );
+    let search = dijkstra( &graph, [start] );
+    assert_eq!( Some(11), search.distance(end) );
+    assert_eq!( Some(3), search.distance(Pos::new(0, 2)) );
+
+    let manhattan = |pos: Pos| { let offset = end - pos; (offset.row.abs() + offset.col.abs()) as usize };
+    let (cost, path) = astar( &graph, start, |pos| pos == end, manhattan ).unwrap();
+    assert_eq!( 11, cost );
+    assert_eq!( 11, path.len() );
+    assert!( path.contains( &Pos::new(0, 2) ) );
+
+    let walls = GridGraph::passable( &mat, |_, (_, cell)| *cell != '#' );
+    assert_eq!( None, astar( &walls, start, |pos| pos == Pos::new(0, 2), |_| 0 ) );
+  }
+}
@@ -0,0 +1,199 @@
+use std::{fmt, io, thread, time::Duration};
+use crate::report::json_string;
+
+// Step by step recording of a simulation : the simulation reports each step to a Recorder, which is either a Trace
+// keeping the frames for replay and export, or `()` when nothing is recorded, so untraced runs pay nothing.
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+pub trait Recorder<S> {
+  /// Records a step, `frame` returning what happened and the resulting state. Only called when actually recording.
+  fn record( &mut self, frame: impl FnOnce() -> (String, S) );
+}
+
+impl<S> Recorder<S> for () {
+  fn record( &mut self, _frame: impl FnOnce() -> (String, S) ) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame<S> {
+  pub step: usize,
+  pub event: String,
+  pub state: S,
+}
+
+/// Recorded frames, up to an optional limit past which steps are only counted.
+#[derive(Debug, Clone)]
+pub struct Trace<S> {
+  frames: Vec<Frame<S>>,
+  limit: Option<usize>,
+  steps: usize,
+}
+
+impl<S> Default for Trace<S> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<S> Trace<S> {
+  pub fn new() -> Self {
+    Self { frames: Vec::new(), limit: None, steps: 0 }
+  }
+
+  /// Trace keeping only the first `limit` frames.
+  pub fn with_limit( limit: usize ) -> Self {
+    Self { limit: Some(limit), ..Self::new() }
+  }
+
+  pub fn push( &mut self, event: impl Into<String>, state: S ) {
+    if self.limit.is_none_or( |limit| self.frames.len() < limit ) {
+      self.frames.push( Frame { step: self.steps, event: event.into(), state } );
+    }
+    self.steps += 1;
+  }
+
+  pub fn frames( &self ) -> &[Frame<S>] {
+    &self.frames
+  }
+
+  /// Number of recorded steps, kept or not.
+  pub fn steps( &self ) -> usize {
+    self.steps
+  }
+
+  pub fn dropped( &self ) -> usize {
+    self.steps - self.frames.len()
+  }
+
+  pub fn map<U>( self, mut f: impl FnMut( S ) -> U ) -> Trace<U> {
+    Trace {
+      frames: self.frames.into_iter().map( |frame| Frame { step: frame.step, event: frame.event, state: f(frame.state) } ).collect(),
+      limit: self.limit,
+      steps: self.steps,
+    }
+  }
+}
+
+impl<S> Recorder<S> for Trace<S> {
+  fn record( &mut self, frame: impl FnOnce() -> (String, S) ) {
+    if self.limit.is_some_and( |limit| self.frames.len() >= limit ) {
+      self.steps += 1;
+      return;
+    }
+    let (event, state) = frame();
+    self.push( event, state );
+  }
+}
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+impl<S: fmt::Display> Trace<S> {
+  /// Plays the frames back in a terminal, clearing the screen before each of them.
+  pub fn replay( &self, writer: &mut impl io::Write, delay: Duration ) -> io::Result<()> {
+    for frame in &self.frames {
+      write!( writer, "{CLEAR_SCREEN}step {}: {}\n{}", frame.step, frame.event, frame.state )?;
+      writer.flush()?;
+      thread::sleep( delay );
+    }
+    Ok(())
+  }
+
+  /// asciicast v2 rendering, one frame every `delay`, see [`Asciicast`].
+  pub fn asciicast( &self, delay: Duration ) -> Asciicast<'_, S> {
+    Asciicast { trace: self, delay }
+  }
+
+  /// One JSON object per frame, see [`JsonLines`].
+  pub fn json_lines( &self ) -> JsonLines<'_, S> {
+    JsonLines( self )
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------------------
+
+/// Recording playable with `asciinema play`, each frame redrawing the whole screen.
+pub struct Asciicast<'a, S> {
+  trace: &'a Trace<S>,
+  delay: Duration,
+}
+
+impl<S: fmt::Display> fmt::Display for Asciicast<'_, S> {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    let screens = self.trace.frames.iter()
+      .map( |frame| format!( "step {}: {}\n{}", frame.step, frame.event, frame.state ) )
+      .collect::<Vec<_>>();
+    let width = screens.iter().flat_map( |screen| screen.lines() ).map( |line| line.chars().count() ).max().unwrap_or( 0 );
+    let height = screens.iter().map( |screen| screen.lines().count() ).max().unwrap_or( 0 );
+
+    writeln!( f, "{{\"version\": 2, \"width\": {}, \"height\": {}}}", width.max(1), height.max(1) )?;
+    for (idx, screen) in screens.iter().enumerate() {
+      let time = self.delay.as_secs_f64() * idx as f64;
+      let output = format!( "{CLEAR_SCREEN}{}", screen.replace( '\n', "\r\n" ) );
+      writeln!( f, "[{time:.6}, \"o\", {}]", json_string(&output) )?;
+    }
+    Ok(())
+  }
+}
+
+/// Event log, one `{"step": n, "event": "...", "state": "..."}` line per frame.
+pub struct JsonLines<'a, S>( pub &'a Trace<S> );
+
+impl<S: fmt::Display> fmt::Display for JsonLines<'_, S> {
+  fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+    for frame in &self.0.frames {
+      writeln!( f, "{{\"step\": {}, \"event\": {}, \"state\": {}}}",
+        frame.step, json_string(&frame.event), json_string(&frame.state.to_string()) )?;
+    }
+    Ok(())
+  }
+}
+
+// =================================================================================================================================
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn count_down( from: usize, recorder: &mut impl Recorder<usize> ) {
+    for value in (0..from).rev() {
+      recorder.record( || (format!( "decrement to {value}" ), value) );
+    }
+  }
+
+  #[test]
+  fn test_record() {
+    count_down( 3, &mut () );
+
+    let mut trace = Trace::new();
+    count_down( 3, &mut trace );
+    assert_eq!( vec![2, 1, 0], trace.frames().iter().map( |frame| frame.state ).collect::<Vec<_>>() );
+    assert_eq!( "decrement to 0", trace.frames()[2].event );
+
+    let mut trace = Trace::with_limit( 2 );
+    count_down( 5, &mut trace );
+    assert_eq!( (2, 5, 3), (trace.frames().len(), trace.steps(), trace.dropped()) );
+  }
+
+  #[test]
+  fn test_exports() {
+    let mut trace = Trace::new();
+    trace.push( "start", "a\"b" );
+    trace.push( "end", "c\nd" );
+
+    let expected = r#"{"step": 0, "event": "start", "state": "a\"b"}
+{"step": 1, "event": "end", "state": "c\nd"}
+"#;
+    assert_eq!( expected, trace.json_lines().to_string() );
+
+    let expected = r#"{"version": 2, "width": 13, "height": 3}
+[0.000000, "o", "\u001b[2J\u001b[Hstep 0: start\r\na\"b"]
+[0.500000, "o", "\u001b[2J\u001b[Hstep 1: end\r\nc\r\nd"]
+"#;
+    assert_eq!( expected, trace.asciicast( Duration::from_millis(500) ).to_string() );
+
+    let mut replay = Vec::new();
+    trace.replay( &mut replay, Duration::ZERO ).unwrap();
+    assert_eq!( "\x1b[2J\x1b[Hstep 0: start\na\"b\x1b[2J\x1b[Hstep 1: end\nc\nd", String::from_utf8(replay).unwrap() );
+  }
+}